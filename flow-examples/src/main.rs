@@ -1,13 +1,14 @@
-use std::collections::HashMap;
 use std::env;
 use std::error::Error;
-use std::fs::File;
 use std::path::Path;
 use std::process::Child;
 use std::thread::sleep;
 use std::time::Duration;
 
-use flow_sdk::algorithms::{self as algo, HashAlgorithm, SignatureAlgorithm};
+use flow_sdk::algorithms::{
+    self as algo, AnyHashAlgorithm, AnySignatureAlgorithm, SignatureAlgorithm,
+};
+use flow_sdk::config::Config;
 use flow_sdk::prelude::*;
 use rustyline::error::ReadlineError;
 
@@ -15,31 +16,6 @@ mod examples;
 
 pub type ExampleAccount = DefaultAccount<TonicHyperFlowClient>;
 
-#[derive(serde::Deserialize, Debug)]
-pub struct AccountKeyConfig {
-    #[serde(rename = "type")]
-    pub ty: String,
-    pub index: u64,
-    #[serde(rename = "signatureAlgorithm")]
-    pub sign_algo: String,
-    #[serde(rename = "hashAlgorithm")]
-    pub hash_algo: String,
-    #[serde(rename = "privateKey")]
-    pub private_key: String,
-}
-
-#[derive(serde::Deserialize, Debug)]
-pub struct AccountConfig {
-    pub address: String,
-    pub key: AccountKeyConfig,
-}
-
-#[derive(serde::Deserialize, Debug)]
-pub struct FlowConfig {
-    pub networks: HashMap<String, String>,
-    pub accounts: HashMap<String, AccountConfig>,
-}
-
 fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     use std::process::Command;
 
@@ -84,45 +60,29 @@ fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     // wait 0.5s for server to start.
     sleep(Duration::from_millis(500));
 
-    let json_file = File::open(json)?;
-
-    let mut cfg: FlowConfig = serde_json::from_reader(json_file).unwrap();
-
-    // println!("{:#?}", cfg);
-
-    let emulator = cfg.networks.remove("emulator").unwrap();
+    let cfg = Config::load_from(json)?;
 
     let rt = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()
         .unwrap();
 
-    rt.block_on(main_inner(cfg, emulator))?;
+    rt.block_on(main_inner(cfg))?;
 
     Ok(())
 }
 
-async fn main_inner(
-    mut cfg: FlowConfig,
-    emulator: String,
-) -> Result<(), Box<dyn Error + Send + Sync>> {
-    let client =
-        TonicHyperFlowClient::connect(format!("http://{}", emulator).try_into().unwrap()).await?;
-    let acc = cfg.accounts.remove("emulator-account").unwrap();
-
-    let addr = hex::decode(acc.address).unwrap();
-
-    assert_eq!(acc.key.ty, "hex");
-    assert_eq!(acc.key.sign_algo, algo::Secp256k1::NAME);
-    assert_eq!(acc.key.hash_algo, algo::Sha3::NAME);
+async fn main_inner(cfg: Config) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let client = cfg.client("emulator").await?;
+    let acc = cfg.account_config("emulator-account")?;
 
-    let sec = hex::decode(acc.key.private_key).unwrap();
-    let secret_key = algo::secp256k1::SecretKey::from_slice(&sec).unwrap();
-    //let public_key = algo::secp256k1::PublicKey::from_secret_key(&algo::secp256k1::Secp256k1::signing_only(), &secret_key);
+    assert_eq!(acc.key.sign_algo, AnySignatureAlgorithm::Secp256k1);
+    assert_eq!(acc.key.hash_algo, AnyHashAlgorithm::Sha3);
 
-    //println!("{}", hex::encode(public_key.serialize_uncompressed()));
+    let sec = cfg.secret_key("emulator-account")?.to_bytes();
+    let secret_key = algo::secp256k1::SecretKey::from_slice(&*sec).unwrap();
 
-    let mut account = DefaultAccount::new(client, &addr, secret_key).await?;
+    let mut account = DefaultAccount::new(client, &*acc.address, secret_key).await?;
 
     println!("Successfully logged in to the service account!");
     println!("type \"help\" for help");
@@ -158,13 +158,11 @@ impl SendTransactionResponse {
     /// To customize the delay and the timeout, refer to [`Finalize`]'s documentation.
     ///
     /// [`Finalize`]: crate::transaction::Finalize
-    pub fn finalize<
-        'a,
-        C: GrpcClient<GetTransactionRequest<&'a [u8]>, TransactionResultResponse>,
-    >(
-        &'a self,
-        client: C,
-    ) -> Finalize<&'a [u8], C> {
+    pub fn finalize<'a, C>(&'a self, client: C) -> Finalize<'a, C>
+    where
+        C: GrpcClient<GetTransactionRequest<&'a [u8]>, TransactionResultResponse> + 'a,
+        C::Error: 'a,
+    {
         Finalize::new(
             &self.id,
             client,
@@ -172,6 +170,20 @@ impl SendTransactionResponse {
             Duration::from_secs(60),
         )
     }
+
+    /// Returns a stream of the statuses of the transaction, which can also wait for a chosen
+    /// status such as [`TransactionStatus::Executed`].
+    ///
+    /// See [`StatusStream`] for the default delay and timeout.
+    ///
+    /// [`StatusStream`]: crate::transaction::StatusStream
+    pub fn status_stream<'a, C>(&'a self, client: C) -> StatusStream<'a, C>
+    where
+        C: GrpcClient<GetTransactionRequest<&'a [u8]>, TransactionResultResponse> + 'a,
+        C::Error: 'a,
+    {
+        StatusStream::new(&self.id, client)
+    }
 }
 
 /// Gets a transaction's details by its id.
@@ -3,26 +3,42 @@
 //! This module contains ways to log in to an account via secret key(s) and an Access API client.
 
 use std::collections::HashMap;
+use std::error::Error as StdError;
 use std::iter::empty;
 use std::marker::PhantomData;
-use std::slice;
+use std::time::Duration;
+
+use cadence_json::ValueOwned;
+use futures_util::StreamExt;
 
 use crate::access::{
     AccountResponse, BlockHeaderResponse, GetAccountAtLatestBlockRequest,
-    GetLatestBlockHeaderRequest, SendTransactionRequest, SendTransactionResponse,
+    GetLatestBlockHeaderRequest, GetTransactionRequest, SendTransactionRequest,
+    SendTransactionResponse, TransactionResultResponse,
 };
+#[cfg(feature = "any-algorithms")]
+use crate::algorithms::{AnyHasher, AnySecretKey, AnySigner};
 use crate::algorithms::{
-    DefaultHasher, DefaultSecretKey, DefaultSigner, FlowHasher, FlowSigner, HashAlgorithm,
-    Signature, SignatureAlgorithm,
+    AsyncFlowSigner, DefaultHasher, DefaultSecretKey, DefaultSigner, FlowHasher, FlowSigner,
+    Signature,
 };
+use crate::cadence_error::{is_proposal_key_error, is_proposal_key_rejection};
 use crate::client::{FlowClient, GrpcClient};
 use crate::entities::AccountKey;
-use crate::error::BoxError;
-use crate::multi::{Party, PartyTransaction};
+use crate::error::{
+    BoxError, CreateAccountError, ResubmitError, SendTransactionError, TonicError,
+    TransactionStatusError,
+};
+use crate::message::encode_account_proof;
+use crate::multi::{Party, PartySignFuture, PartySigner, PartyTransaction, SigningParty};
+use crate::proposer::ProposalKey;
 use crate::protobuf::Seal;
 use crate::sign::{KeyIdIter, MkSigIter, Multi, One, SignIter, SignMethod};
 use crate::transaction::rlp::rlp_encode_transaction_envelope;
-use crate::transaction::{ProposalKeyE, SignatureE, TransactionE, TransactionHeader};
+use crate::transaction::{
+    CreateAccountTransaction, Finalize, ProposalKeyE, SignatureE, StatusStream, TransactionE,
+    TransactionHeader, TransactionLimits, TransactionStatus,
+};
 
 const PADDED_LEN: usize = 32;
 
@@ -30,11 +46,174 @@ const PADDED_LEN: usize = 32;
 pub const PADDED_TRANSACTION_DOMAIN_TAG: [u8; PADDED_LEN] =
     padded::<PADDED_LEN>(b"FLOW-V0.0-transaction");
 
+/// The user domain tag, padded to 32 bytes. Used when signing arbitrary messages.
+pub const PADDED_USER_DOMAIN_TAG: [u8; PADDED_LEN] = padded::<PADDED_LEN>(b"FLOW-V0.0-user");
+
+/// The FCL account proof domain tag, padded to 32 bytes.
+pub const PADDED_ACCOUNT_PROOF_DOMAIN_TAG: [u8; PADDED_LEN] =
+    padded::<PADDED_LEN>(b"FCL-ACCOUNT-PROOF-V0.0");
+
 pub use crate::error::AccountError as Error;
 
 /// An account that uses the default signing and hashing algorithms.
 pub type DefaultAccount<Client> = Account<Client>;
 
+/// An account whose keys can use any of the supported signing and hashing algorithms, selected
+/// at runtime by each [`AnySecretKey`].
+#[cfg(feature = "any-algorithms")]
+pub type AnyAccount<Client> = Account<Client, AnySecretKey, AnySigner, AnyHasher>;
+
+/// Options for creating accounts with [`Account::create_account`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CreateAccountOptions {
+    /// The delay between queries about the result of the transaction.
+    pub delay: Duration,
+
+    /// How long to wait for the transaction to be sealed.
+    pub timeout: Duration,
+}
+
+impl Default for CreateAccountOptions {
+    /// Uses the same delay and timeout as [`SendTransactionResponse::finalize`].
+    #[inline]
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_secs(2),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Options for resubmitting transactions with [`Account::send_transaction_header_resubmit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResubmitOptions {
+    /// How many times the transaction is submitted at most.
+    pub max_attempts: u32,
+
+    /// The status to wait for, such as [`TransactionStatus::Executed`] for faster confirmations.
+    pub target: TransactionStatus,
+
+    /// The delay between queries about the result of each transaction.
+    pub delay: Duration,
+
+    /// How long to wait for each transaction to reach the target status.
+    pub timeout: Duration,
+}
+
+impl Default for ResubmitOptions {
+    /// Submits at most 3 times, waiting for the transaction to be sealed with the same delay and
+    /// timeout as [`SendTransactionResponse::finalize`].
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            target: TransactionStatus::Sealed,
+            delay: Duration::from_secs(2),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// A transaction that reached its target status after being submitted by
+/// [`Account::send_transaction_header_resubmit`].
+#[derive(Debug)]
+pub struct Resubmitted {
+    /// The ID of the transaction that reached the target status.
+    pub id: Vec<u8>,
+
+    /// How many times the transaction was submitted.
+    pub attempts: u32,
+
+    /// The result of the transaction.
+    pub result: TransactionResultResponse,
+}
+
+/// Options for sending transactions with [`Account::send_transaction_header_with`].
+///
+/// By default, the gas limit is `1000`, the latest sealed block is the reference block, the
+/// sequence number of the proposal key is queried from the network, and the sending account is the
+/// proposer, the payer and the only authorizer.
+///
+/// Other accounts can authorize or pay for the transaction, they sign it before it is sent.
+#[derive(Clone)]
+pub struct SendOptions<'a> {
+    gas_limit: u64,
+    reference_block: Option<Box<[u8]>>,
+    proposal_key_id: Option<u32>,
+    sequence_number: Option<u64>,
+    authorizers: Vec<&'a dyn PartySigner>,
+    payer: Option<&'a dyn PartySigner>,
+}
+
+impl<'a> SendOptions<'a> {
+    /// Creates the default options.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            gas_limit: 1000,
+            reference_block: None,
+            proposal_key_id: None,
+            sequence_number: None,
+            authorizers: Vec::new(),
+            payer: None,
+        }
+    }
+
+    /// Sets the gas limit of the transaction.
+    pub fn gas_limit(mut self, limit: u64) -> Self {
+        self.gas_limit = limit;
+        self
+    }
+
+    /// Sets the ID of the reference block of the transaction.
+    pub fn reference_block(mut self, reference_block: impl Into<Box<[u8]>>) -> Self {
+        self.reference_block = Some(reference_block.into());
+        self
+    }
+
+    /// Sets the sequence number of the proposal key, instead of querying it from the network.
+    pub fn sequence_number(mut self, sequence_number: u64) -> Self {
+        self.sequence_number = Some(sequence_number);
+        self
+    }
+
+    /// Sets the index of the proposal key, instead of the primary key of the sending account.
+    ///
+    /// The sending account must sign with this key.
+    pub fn proposal_key_id(mut self, key_id: u32) -> Self {
+        self.proposal_key_id = Some(key_id);
+        self
+    }
+
+    /// Sets the index and the sequence number of the proposal key to a key handed out by a
+    /// [`ProposerPool`].
+    ///
+    /// [`ProposerPool`]: crate::proposer::ProposerPool
+    pub fn proposal_key(self, key: &ProposalKey<'_>) -> Self {
+        self.proposal_key_id(key.key_id())
+            .sequence_number(key.sequence_number())
+    }
+
+    /// Appends an account which authorizes the transaction along with the sending account.
+    pub fn authorizer(mut self, authorizer: &'a dyn PartySigner) -> Self {
+        self.authorizers.push(authorizer);
+        self
+    }
+
+    /// Sets the account that pays for the transaction instead of the sending account.
+    pub fn payer(mut self, payer: &'a dyn PartySigner) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+}
+
+impl Default for SendOptions<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Clone)]
 /// An account.
 ///
@@ -51,9 +230,24 @@ pub struct Account<
     sign_method: SignMethod<SecretKey>,
     signer: Signer,
     client: FlowClient<Client>,
+    sequence_number: TrackedSequenceNumber,
+    limits: Option<TransactionLimits>,
     _pd: PhantomData<Hasher>,
 }
 
+/// The sequence number of the primary key, when it is tracked locally.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TrackedSequenceNumber {
+    /// The sequence number is queried from the network for every transaction.
+    Untracked,
+
+    /// The sequence number is queried from the network for the next transaction.
+    Unknown,
+
+    /// The sequence number to propose the next transaction with.
+    Known(u32),
+}
+
 impl<Cl, Sk, Sn, Hs> Account<Cl, Sk, Sn, Hs> {
     /// Returns the address of this account.
     #[inline]
@@ -96,11 +290,73 @@ impl<Cl, Sk, Sn, Hs> Account<Cl, Sk, Sn, Hs> {
     pub fn primary_key_id(&self) -> u32 {
         self.sign_method.primary_key_id()
     }
+
+    /// Enables or disables tracking the sequence number of the primary key locally.
+    ///
+    /// When enabled, the sequence number is queried from the network once, then incremented after
+    /// every transaction that is successfully submitted with the primary key as the proposal key.
+    /// This allows sending transactions without waiting for the previous ones to be sealed.
+    ///
+    /// Transactions that expire or fail because of their proposal key do not increment the
+    /// sequence number on the network, pass their results to
+    /// [`Account::update_sequence_number`] to resync it.
+    pub fn track_sequence_number(&mut self, track: bool) {
+        self.sequence_number = if track {
+            TrackedSequenceNumber::Unknown
+        } else {
+            TrackedSequenceNumber::Untracked
+        };
+    }
+
+    /// Returns the sequence number that the next transaction will be proposed with, if it is
+    /// tracked locally and known.
+    #[inline]
+    pub fn tracked_sequence_number(&self) -> Option<u32> {
+        match self.sequence_number {
+            TrackedSequenceNumber::Known(sequence_number) => Some(sequence_number),
+            _ => None,
+        }
+    }
+
+    /// Queries the sequence number from the network again before the next transaction, if it is
+    /// tracked locally.
+    pub fn resync_sequence_number(&mut self) {
+        if self.sequence_number != TrackedSequenceNumber::Untracked {
+            self.sequence_number = TrackedSequenceNumber::Unknown;
+        }
+    }
+
+    /// Sets the limits that transactions are checked against before they are sent, or disables
+    /// the checks with `None`.
+    ///
+    /// The limits of the Flow networks are checked by default.
+    pub fn set_transaction_limits(&mut self, limits: Option<TransactionLimits>) {
+        self.limits = limits;
+    }
+
+    /// Returns the limits that transactions are checked against before they are sent.
+    #[inline]
+    pub fn transaction_limits(&self) -> Option<&TransactionLimits> {
+        self.limits.as_ref()
+    }
+
+    /// Updates the locally tracked sequence number with the result of a transaction proposed with
+    /// the primary key.
+    ///
+    /// The sequence number is resynced if the transaction expired or failed because of its
+    /// proposal key, such as when the sequence number did not match.
+    pub fn update_sequence_number(&mut self, result: &TransactionResultResponse) {
+        if result.status == TransactionStatus::Expired
+            || is_proposal_key_error(&result.error_message)
+        {
+            self.resync_sequence_number();
+        }
+    }
 }
 
 impl<Client, SecretKey, Signer, Hasher> Account<Client, SecretKey, Signer, Hasher>
 where
-    Signer: FlowSigner<SecretKey = SecretKey>,
+    Signer: AsyncFlowSigner<SecretKey = SecretKey>,
     Hasher: FlowHasher,
 {
     ///////////////////////
@@ -134,9 +390,8 @@ where
 
         let mut account_key = None;
 
-        let signer = Signer::new();
-        let public_key = signer.to_public_key(&secret_key);
-        let serialized = signer.serialize_public_key(&public_key);
+        let signer = Signer::new_signer();
+        let serialized = signer.public_key_bytes(&secret_key);
 
         for key in keys {
             if *key.public_key == serialized {
@@ -161,7 +416,9 @@ where
             return Err(Error::NotEnoughWeight);
         }
 
-        if Signer::Algorithm::CODE != sign_algo || Hasher::Algorithm::CODE != hash_algo {
+        if !signer.is_compatible_key(&secret_key, sign_algo, hash_algo)
+            || !Hasher::is_compatible(hash_algo)
+        {
             return Err(Error::AlgoMismatch);
         }
 
@@ -173,6 +430,8 @@ where
             }),
             signer,
             client,
+            sequence_number: TrackedSequenceNumber::Untracked,
+            limits: Some(TransactionLimits::new()),
             _pd: PhantomData,
         })
     }
@@ -216,22 +475,32 @@ where
             "primary key must be valid"
         );
 
-        let signer = Signer::new();
+        let signer = Signer::new_signer();
         let mut primary_key_idx = usize::MAX;
         let mut total_weight = 0;
         let mut found_keys = Vec::new();
 
-        let mut add_key = |key_index: usize, key_id, weight| {
+        let mut add_key = |key_index: usize, key: &AccountKey| {
+            let secret_key = &secret_keys[key_index];
+
+            if !signer.is_compatible_key(secret_key, key.sign_algo, key.hash_algo)
+                || !Hasher::is_compatible(key.hash_algo)
+            {
+                return Err(Error::AlgoMismatch);
+            }
+
             if key_index == primary_index {
                 primary_key_idx = found_keys.len();
             }
 
             found_keys.push(One {
-                key_id,
-                key: secret_keys[key_index].clone(),
+                key_id: key.index,
+                key: secret_key.clone(),
             });
 
-            total_weight += weight;
+            total_weight += key.weight;
+
+            Ok(())
         };
 
         if secret_keys.len() > 10 {
@@ -239,17 +508,12 @@ where
             let mut public_keys_to_find: HashMap<_, _> = secret_keys
                 .iter()
                 .enumerate()
-                .map(|(idx, secret_key)| {
-                    (
-                        signer.serialize_public_key(&signer.to_public_key(secret_key)),
-                        idx,
-                    )
-                })
+                .map(|(idx, secret_key)| (signer.public_key_bytes(secret_key), idx))
                 .collect();
 
             for key in keys {
                 if let Some(key_index) = public_keys_to_find.remove(&*key.public_key) {
-                    add_key(key_index, key.index, key.weight);
+                    add_key(key_index, &key)?;
                 }
             }
 
@@ -260,19 +524,18 @@ where
             // Hashing can be expensive for small sets.
             let mut public_keys_to_find: Vec<_> = secret_keys
                 .iter()
-                .map(|sk| signer.to_public_key(sk))
-                .map(|pk| signer.serialize_public_key(&pk))
+                .map(|sk| signer.public_key_bytes(sk))
+                .enumerate()
                 .collect();
 
             for key in keys {
-                if let Some((index, _)) = public_keys_to_find
+                if let Some(position) = public_keys_to_find
                     .iter()
-                    .enumerate()
-                    .find(|(_, pubkey)| *pubkey == &*key.public_key)
+                    .position(|(_, pubkey)| pubkey == &*key.public_key)
                 {
                     // Do not allow duplicate secret keys
-                    public_keys_to_find.swap_remove(index);
-                    add_key(index, key.index, key.weight);
+                    let (key_index, _) = public_keys_to_find.swap_remove(position);
+                    add_key(key_index, &key)?;
                 }
             }
 
@@ -293,6 +556,8 @@ where
             }),
             signer,
             client,
+            sequence_number: TrackedSequenceNumber::Untracked,
+            limits: Some(TransactionLimits::new()),
             _pd: PhantomData,
         })
     }
@@ -315,36 +580,85 @@ where
         Self {
             address,
             sign_method,
-            signer: Signer::new(),
+            signer: Signer::new_signer(),
             client: FlowClient::new(client),
+            sequence_number: TrackedSequenceNumber::Untracked,
+            limits: Some(TransactionLimits::new()),
             _pd: PhantomData,
         }
     }
 
-    ////////////////////
-    // INFORMATION
+    //////////////////
+    // ASYNC SIGNING
 
-    /// Queries the sequence number for the primary key from the network.
-    pub async fn primary_key_sequence_number<'a>(&'a mut self) -> Result<u32, BoxError>
+    /// Creates signature(s) asynchronously using this account's key(s), consuming a populated
+    /// hasher.
+    pub async fn sign_async(
+        &self,
+        hasher: Hasher,
+    ) -> Result<Vec<Signer::Signature>, Signer::Error> {
+        SignIter::new(hasher.finalize_digest(), &self.signer, &self.sign_method)
+            .collect_async()
+            .await
+    }
+
+    /// Creates signature(s) asynchronously using this account's key(s), signing provided data.
+    pub async fn sign_data_async(
+        &self,
+        data: impl AsRef<[u8]>,
+    ) -> Result<Vec<Signer::Signature>, Signer::Error> {
+        let mut hasher = Hasher::new();
+        hasher.update(&data);
+        self.sign_async(hasher).await
+    }
+
+    /// Signs a party asynchronously, assuming that you have confirmed all the details of the party.
+    pub async fn sign_party_async<P: Party<Hasher>>(
+        &self,
+        party: &mut P,
+    ) -> Result<(), Signer::Error>
     where
-        Client: GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+        Signer::Signature: Signature<Serialized = [u8; 64]>,
     {
-        let address = &*self.address;
-        let public_key = self.signer.serialize_public_key(&self.primary_public_key());
-
-        let acc = self
-            .client
-            .account_at_latest_block(address)
-            .await
-            .map_err(Into::into)?;
-        for key in acc.keys {
-            if *key.public_key == public_key {
-                return Ok(key.sequence_number);
-            }
+        let signatures = self.sign_async(party.payload()).await?;
+        let key_ids = self.sign_method.key_ids();
+        for (sig, key_id) in signatures.into_iter().zip(key_ids) {
+            party.add_payload_signature(self.address.clone(), key_id, sig.serialize())
         }
-        unreachable!();
+        Ok(())
+    }
+
+    /// Signs the party asynchronously as the payer, thereby converting the party into a
+    /// transaction, ready to be sent.
+    pub async fn sign_party_as_payer_async<P: Party<Hasher>>(
+        &self,
+        party: P,
+    ) -> Result<PartyTransaction<Box<[u8]>, [u8; 64]>, Signer::Error>
+    where
+        Signer::Signature: Signature<Serialized = [u8; 64]>,
+    {
+        assert_eq!(&*self.address, party.payer());
+        let signatures = self.sign_async(party.envelope()).await?;
+        let key_ids = self.sign_method.key_ids();
+
+        Ok(party.into_transaction_with_envelope_signatures(
+            signatures
+                .into_iter()
+                .zip(key_ids)
+                .map(|(sig, key_id)| SignatureE {
+                    address: self.address.clone(),
+                    key_id,
+                    signature: sig.serialize(),
+                }),
+        ))
     }
+}
 
+impl<Client, SecretKey, Signer, Hasher> Account<Client, SecretKey, Signer, Hasher>
+where
+    Signer: FlowSigner<SecretKey = SecretKey>,
+    Hasher: FlowHasher,
+{
     //////////////////
     // SIGNING
 
@@ -360,6 +674,36 @@ where
         self.sign(hasher)
     }
 
+    /// Signs a message prefixed with the user domain tag using this account's key(s).
+    ///
+    /// The signatures can be verified by Cadence and FCL, or with
+    /// `message::verify_user_signatures`, behind the `any-algorithms` feature.
+    pub fn sign_user_message(
+        &self,
+        message: impl AsRef<[u8]>,
+    ) -> MkSigIter<'_, KeyIdIter<'_, SecretKey>, SignIter<'_, Signer>> {
+        let mut hasher = Hasher::new();
+        hasher.update(&PADDED_USER_DOMAIN_TAG);
+        hasher.update(&message);
+        MkSigIter::new(&self.address, self.sign_method.key_ids(), self.sign(hasher))
+    }
+
+    /// Signs an FCL account proof using this account's key(s), proving to the app that this account
+    /// is controlled by the signer.
+    ///
+    /// The signatures can be verified with `message::verify_account_proof`, behind the
+    /// `any-algorithms` feature.
+    pub fn sign_account_proof(
+        &self,
+        app_identifier: &str,
+        nonce: impl AsRef<[u8]>,
+    ) -> MkSigIter<'_, KeyIdIter<'_, SecretKey>, SignIter<'_, Signer>> {
+        let mut hasher = Hasher::new();
+        hasher.update(&PADDED_ACCOUNT_PROOF_DOMAIN_TAG);
+        hasher.update(&encode_account_proof(app_identifier, &self.address, nonce));
+        MkSigIter::new(&self.address, self.sign_method.key_ids(), self.sign(hasher))
+    }
+
     /// Signs a party, assuming that you have confirmed all the details of the party.
     pub fn sign_party<P: Party<Hasher>>(&self, party: &mut P)
     where
@@ -442,57 +786,177 @@ where
         )
     }
 
+    /// Creates a new account with the public keys at full weight, with this account being the
+    /// proposer, the payer and the only authorizer of the transaction.
+    ///
+    /// The keys of the new account use the same algorithms as this account's signer and hasher.
+    ///
+    /// Waits for the transaction to be sealed and returns the address of the created account.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    ///  - the client returns any errors while making requests
+    ///  - the transaction failed, expired, or was not sealed within the timeout
+    ///  - no `flow.AccountCreated` event was emitted by the transaction.
+    pub async fn create_account(
+        &mut self,
+        public_keys: &[Signer::PublicKey],
+        options: CreateAccountOptions,
+    ) -> Result<Box<[u8]>, CreateAccountError>
+    where
+        Client: for<'b> GrpcClient<GetAccountAtLatestBlockRequest<&'b [u8]>, AccountResponse>,
+        Client: GrpcClient<GetLatestBlockHeaderRequest, BlockHeaderResponse>,
+        Client: for<'b> GrpcClient<GetTransactionRequest<&'b [u8]>, TransactionResultResponse>,
+        for<'b> Client: GrpcClient<
+            SendTransactionRequest<
+                &'b [u8],
+                Vec<&'b [u8]>,
+                &'b [u8],
+                &'b [u8],
+                &'b [u8],
+                [&'b [u8]; 1],
+                [SignatureE<&'b [u8], &'b [u8]>; 0],
+                Vec<SignatureE<&'b [u8], <Signer::Signature as Signature>::Serialized>>,
+            >,
+            SendTransactionResponse,
+        >,
+    {
+        let header =
+            CreateAccountTransaction { public_keys }.to_header::<Signer, Hasher>(&self.signer);
+        self.create_account_with_header(&header, options).await
+    }
+
+    ///////////////
+    /// PRIVATE
+
+    #[allow(clippy::too_many_arguments)]
+    fn sign_transaction_header_<'a, 'b, Arguments>(
+        key_id: u32,
+        address: &[u8],
+        signer: &'a Signer,
+        method: &'a SignMethod<SecretKey>,
+        header: &'b TransactionHeader<Arguments>,
+        reference_block_id: impl AsRef<[u8]>,
+        sequence_number: u64,
+        gas_limit: u64,
+    ) -> SignIter<'a, Signer>
+    where
+        &'b Arguments: IntoIterator,
+        <&'b Arguments as IntoIterator>::IntoIter: ExactSizeIterator,
+        <<&'b Arguments as IntoIterator>::IntoIter as Iterator>::Item: AsRef<[u8]>,
+    {
+        Self::sign_transaction_(
+            key_id,
+            address,
+            signer,
+            method,
+            &header.script.as_ref(),
+            &header.arguments,
+            reference_block_id,
+            sequence_number,
+            gas_limit,
+        )
+    }
+}
+
+impl<Client, SecretKey, Signer, Hasher> Account<Client, SecretKey, Signer, Hasher>
+where
+    Signer: AsyncFlowSigner<SecretKey = SecretKey>,
+    Hasher: FlowHasher,
+{
+    ////////////////////
+    // INFORMATION
+
+    /// Queries the sequence number for the primary key from the network.
+    ///
+    /// If the sequence number is tracked locally, see [`Account::track_sequence_number`], it is
+    /// only queried when it is not known.
+    ///
+    /// Returns [`SendTransactionError::ProposalKeyNotFound`] if the primary key is not on the
+    /// account, for example after it was revoked.
+    pub async fn primary_key_sequence_number<'a>(&'a mut self) -> Result<u32, SendTransactionError>
+    where
+        Client: GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    {
+        if let TrackedSequenceNumber::Known(sequence_number) = self.sequence_number {
+            return Ok(sequence_number);
+        }
+
+        let address = &*self.address;
+        let public_key = self
+            .signer
+            .public_key_bytes(self.sign_method.primary_secret_key());
+
+        let acc = self
+            .client
+            .account_at_latest_block(address)
+            .await
+            .map_err(Into::into)?;
+        for key in acc.keys {
+            if *key.public_key == public_key {
+                if self.sequence_number == TrackedSequenceNumber::Unknown {
+                    self.sequence_number = TrackedSequenceNumber::Known(key.sequence_number);
+                }
+                return Ok(key.sequence_number);
+            }
+        }
+
+        Err(SendTransactionError::ProposalKeyNotFound(
+            self.primary_key_id(),
+        ))
+    }
+
+    //////////////////
+    // SENDING
+
     /// Send a transaction to the network. Signs the transaction header with a gas limit of 1000
     /// and using the latest sealed block as a reference.
     ///
-    /// Note that this does not increment the sequence number.
+    /// Use [`Account::send_transaction_header_with`] to send with other [`SendOptions`].
+    ///
+    /// The sequence number is queried from the network, unless it is tracked locally, see
+    /// [`Account::track_sequence_number`].
     ///
     /// # Errors
     ///
-    /// This function returns an error if the client returns any errors when making requests.
+    /// This function returns an error if:
+    ///
+    ///  - the transaction exceeds the limits set with [`Account::set_transaction_limits`]
+    ///  - the client returns any errors when making requests
+    ///  - a signer returns any errors
+    ///  - the proposal key could not be found on the account.
     pub async fn send_transaction_header<'a, Arguments, Argument>(
         &'a mut self,
         transaction: &'a TransactionHeader<Arguments>,
-    ) -> Result<SendTransactionResponse, BoxError>
+    ) -> Result<SendTransactionResponse, SendTransactionError>
     where
         Client: for<'b> GrpcClient<GetAccountAtLatestBlockRequest<&'b [u8]>, AccountResponse>,
         Client: GrpcClient<GetLatestBlockHeaderRequest, BlockHeaderResponse>,
         for<'b> Client: GrpcClient<
             SendTransactionRequest<
                 &'b [u8],
-                &'b SliceHelper<Argument>,
+                Vec<&'b [u8]>,
                 &'b [u8],
                 &'b [u8],
                 &'b [u8],
                 [&'b [u8]; 1],
                 [SignatureE<&'b [u8], &'b [u8]>; 0],
-                EmitRefAndDropOnNext<
-                    SignatureE<&'b [u8], <Signer::Signature as Signature>::Serialized>,
-                    MkSigIter<'b, KeyIdIter<'b, Signer::SecretKey>, SignIter<'b, Signer>>,
-                >,
+                Vec<SignatureE<&'b [u8], <Signer::Signature as Signature>::Serialized>>,
             >,
             SendTransactionResponse,
         >,
         Arguments: AsRef<[Argument]>,
         Argument: AsRef<[u8]>,
-        &'a Arguments: IntoIterator,
-        <&'a Arguments as IntoIterator>::IntoIter: ExactSizeIterator,
-        <<&'a Arguments as IntoIterator>::IntoIter as Iterator>::Item: AsRef<[u8]>,
     {
-        let address = &*self.address;
-        let acc = self
-            .client
-            .account_at_latest_block(address)
-            .await
-            .map_err(Into::into)?;
-        let pub_key = self.primary_public_key();
-        let pub_key = self.signer.serialize_public_key(&pub_key);
-        let key = acc
-            .keys
-            .into_iter()
-            .find(|key| *key.public_key == pub_key)
-            .unwrap();
-        let sequence_number = key.sequence_number as u64;
+        let gas_limit = 1000;
+        if let Some(limits) = &self.limits {
+            limits.check_gas_limit(gas_limit)?;
+            limits.check_header(transaction)?;
+        }
+
+        let sequence_number = self.next_sequence_number(self.primary_key_id()).await?;
 
         let latest_block = self
             .client
@@ -501,25 +965,35 @@ where
             .map_err(Into::into)?;
 
         let reference_block_id = &*latest_block.id;
-        let gas_limit = 1000;
-        let sig = Self::sign_transaction_header_(
+        let signatures = Self::sign_transaction_(
             self.primary_key_id(),
             &self.address,
             &self.signer,
             &self.sign_method,
-            transaction,
+            transaction.script.as_ref(),
+            transaction.arguments.as_ref(),
             reference_block_id,
             sequence_number,
             gas_limit,
-        );
+        )
+        .collect_async()
+        .await
+        .map_err(|e| SendTransactionError::Signing(e.into()))?;
 
-        let envelope_signatures = EmitRefAndDropOnNext(
-            MkSigIter::new(&self.address, self.sign_method.key_ids(), sig),
-            PhantomData,
-        );
+        let envelope_signatures: Vec<_> = MkSigIter::new(
+            &self.address,
+            self.sign_method.key_ids(),
+            signatures.into_iter(),
+        )
+        .collect();
         let transaction = TransactionE {
             script: transaction.script.as_ref().as_ref(),
-            arguments: SliceHelper::new_ref(transaction.arguments.as_ref()),
+            arguments: transaction
+                .arguments
+                .as_ref()
+                .iter()
+                .map(AsRef::as_ref)
+                .collect::<Vec<&[u8]>>(),
             reference_block_id,
             gas_limit,
             proposal_key: ProposalKeyE {
@@ -533,11 +1007,371 @@ where
             envelope_signatures,
         };
 
-        Ok(self
+        if let Some(limits) = &self.limits {
+            limits.check_transaction(&transaction)?;
+        }
+
+        let result = self
             .client
             .send_transaction(transaction)
             .await
-            .map_err(Into::into)?)
+            .map_err(Into::into);
+        self.submitted(result)
+    }
+
+    /// Send a transaction to the network, with options for the gas limit, the reference block, the
+    /// sequence number of the proposal key, and other accounts that authorize or pay for the
+    /// transaction.
+    ///
+    /// This account is always the proposer and the first authorizer.
+    ///
+    /// If the sequence number is not set, it is queried from the network unless the proposal key
+    /// is the primary key and its sequence number is tracked locally, see
+    /// [`Account::track_sequence_number`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    ///  - the transaction exceeds the limits set with [`Account::set_transaction_limits`]
+    ///  - the client returns any errors when making requests
+    ///  - a signer returns any errors
+    ///  - the sequence number is not set and the proposal key could not be found on the account.
+    pub async fn send_transaction_header_with<Arguments, Argument>(
+        &mut self,
+        transaction: &TransactionHeader<Arguments>,
+        options: SendOptions<'_>,
+    ) -> Result<SendTransactionResponse, SendTransactionError>
+    where
+        Client: for<'b> GrpcClient<GetAccountAtLatestBlockRequest<&'b [u8]>, AccountResponse>,
+        Client: GrpcClient<GetLatestBlockHeaderRequest, BlockHeaderResponse>,
+        Client: GrpcClient<
+            SendTransactionRequest<
+                Box<[u8]>,
+                Vec<Box<[u8]>>,
+                Box<[u8]>,
+                Box<[u8]>,
+                Box<[u8]>,
+                Vec<Box<[u8]>>,
+                Vec<SignatureE<Box<[u8]>, [u8; 64]>>,
+                Vec<SignatureE<Box<[u8]>, [u8; 64]>>,
+            >,
+            SendTransactionResponse,
+        >,
+        Signer::Signature: Signature<Serialized = [u8; 64]>,
+        Arguments: AsRef<[Argument]>,
+        Argument: AsRef<[u8]>,
+    {
+        let SendOptions {
+            gas_limit,
+            reference_block,
+            proposal_key_id,
+            sequence_number,
+            authorizers,
+            payer,
+        } = options;
+
+        if let Some(limits) = &self.limits {
+            limits.check_gas_limit(gas_limit)?;
+            limits.check_header(transaction)?;
+        }
+
+        let proposal_key_id = proposal_key_id.unwrap_or_else(|| self.primary_key_id());
+        let tracked = sequence_number.is_none() && proposal_key_id == self.primary_key_id();
+        let sequence_number = match sequence_number {
+            Some(sequence_number) => sequence_number,
+            None => self.next_sequence_number(proposal_key_id).await?,
+        };
+
+        let reference_block = match reference_block {
+            Some(reference_block) => reference_block,
+            None => {
+                self.client
+                    .latest_block_header(Seal::Sealed)
+                    .await
+                    .map_err(Into::into)?
+                    .id
+            }
+        };
+
+        let payer_address: Box<[u8]> = match payer {
+            Some(payer) => payer.address().into(),
+            None => self.address.clone(),
+        };
+
+        let authorizer_addresses = std::iter::once(self.address.clone())
+            .chain(
+                authorizers
+                    .iter()
+                    .map(|authorizer| authorizer.address().into()),
+            )
+            .collect();
+
+        let mut party = SigningParty::new(
+            transaction.script.as_ref().into(),
+            transaction
+                .arguments
+                .as_ref()
+                .iter()
+                .map(|argument| argument.as_ref().into())
+                .collect(),
+            reference_block,
+            gas_limit,
+            self.address.clone(),
+            proposal_key_id.into(),
+            sequence_number,
+            payer_address.clone(),
+            authorizer_addresses,
+        );
+
+        // Every account except the payer signs the payload, the payer signs the envelope.
+        if self.address != payer_address {
+            self.sign_party_async(&mut party)
+                .await
+                .map_err(|e| SendTransactionError::Signing(e.into()))?;
+        }
+
+        for authorizer in &authorizers {
+            if authorizer.address() != &*payer_address {
+                authorizer
+                    .sign_payload(&mut party)
+                    .await
+                    .map_err(SendTransactionError::Signing)?;
+            }
+        }
+
+        let envelope_signatures = payer
+            .unwrap_or(self)
+            .sign_envelope(&party)
+            .await
+            .map_err(SendTransactionError::Signing)?;
+        let transaction =
+            Party::<Hasher>::into_transaction_with_envelope_signatures(party, envelope_signatures);
+
+        if let Some(limits) = &self.limits {
+            limits.check_transaction(&transaction)?;
+        }
+
+        let result = self
+            .client
+            .send_transaction(transaction)
+            .await
+            .map_err(Into::into);
+
+        if tracked {
+            self.submitted(result)
+        } else {
+            Ok(result.map_err(BoxError::from)?)
+        }
+    }
+
+    /// Sends a transaction that creates an account, such as a [`CreateAccountTransaction`], with
+    /// this account being the proposer, the payer and the only authorizer.
+    ///
+    /// Waits for the transaction to be sealed and returns the address of the created account.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    ///  - the transaction could not be sent, see [`Account::send_transaction_header`]
+    ///  - the client returns any errors while making requests
+    ///  - the transaction failed, expired, or was not sealed within the timeout
+    ///  - no `flow.AccountCreated` event was emitted by the transaction.
+    pub async fn create_account_with_header<Arguments, Argument>(
+        &mut self,
+        header: &TransactionHeader<Arguments>,
+        options: CreateAccountOptions,
+    ) -> Result<Box<[u8]>, CreateAccountError>
+    where
+        Client: for<'b> GrpcClient<GetAccountAtLatestBlockRequest<&'b [u8]>, AccountResponse>,
+        Client: GrpcClient<GetLatestBlockHeaderRequest, BlockHeaderResponse>,
+        Client: for<'b> GrpcClient<GetTransactionRequest<&'b [u8]>, TransactionResultResponse>,
+        for<'b> Client: GrpcClient<
+            SendTransactionRequest<
+                &'b [u8],
+                Vec<&'b [u8]>,
+                &'b [u8],
+                &'b [u8],
+                &'b [u8],
+                [&'b [u8]; 1],
+                [SignatureE<&'b [u8], &'b [u8]>; 0],
+                Vec<SignatureE<&'b [u8], <Signer::Signature as Signature>::Serialized>>,
+            >,
+            SendTransactionResponse,
+        >,
+        Arguments: AsRef<[Argument]>,
+        Argument: AsRef<[u8]>,
+    {
+        let response = self.send_transaction_header(header).await?;
+
+        let result = Finalize::new(
+            &response.id,
+            &mut self.client,
+            options.delay,
+            options.timeout,
+        )
+        .await
+        .map_err(|e| CreateAccountError::Custom(e.into()))?
+        .ok_or(CreateAccountError::TimedOut)?;
+
+        self.update_sequence_number(&result);
+
+        if result.status == TransactionStatus::Expired {
+            return Err(CreateAccountError::Expired);
+        }
+
+        if result.status_code != 0 {
+            return Err(CreateAccountError::Reverted {
+                status_code: result.status_code,
+                error_message: result.error_message,
+            });
+        }
+
+        result
+            .events
+            .iter()
+            .filter(|event| event.ty == "flow.AccountCreated")
+            .find_map(|event| match event.parse_payload_as_value() {
+                Ok(ValueOwned::Event(payload)) => payload
+                    .find_field("address")
+                    .and_then(ValueOwned::as_address)
+                    .map(|address| address.data.clone()),
+                _ => None,
+            })
+            .ok_or(CreateAccountError::NoAccountCreated)
+    }
+
+    /// Sends a transaction with [`Account::send_transaction_header_with`] and waits for it to
+    /// reach the target status, resubmitting it if it expired or was dropped.
+    ///
+    /// A transaction is dropped if the access node reports that it does not know about it, or if
+    /// it was rejected because of its proposal key. Resubmitted transactions are
+    /// signed again with the latest sealed block as the reference block, and with the sequence
+    /// number of the proposal key queried from the network. A dropped transaction and its
+    /// resubmission share the same sequence number, so only one of them can be executed.
+    ///
+    /// The reference block and the sequence number of the options are only used for the first
+    /// attempt.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    ///  - the client returns any errors while making requests
+    ///  - a transaction could not be sent, or failed to execute
+    ///  - a transaction did not reach the target status within the timeout
+    ///  - every attempt expired or was dropped.
+    pub async fn send_transaction_header_resubmit<Arguments, Argument>(
+        &mut self,
+        transaction: &TransactionHeader<Arguments>,
+        mut send_options: SendOptions<'_>,
+        options: ResubmitOptions,
+    ) -> Result<Resubmitted, ResubmitError>
+    where
+        Client: for<'b> GrpcClient<GetAccountAtLatestBlockRequest<&'b [u8]>, AccountResponse>,
+        Client: GrpcClient<GetLatestBlockHeaderRequest, BlockHeaderResponse>,
+        Client: for<'b> GrpcClient<GetTransactionRequest<&'b [u8]>, TransactionResultResponse>,
+        Client: GrpcClient<
+            SendTransactionRequest<
+                Box<[u8]>,
+                Vec<Box<[u8]>>,
+                Box<[u8]>,
+                Box<[u8]>,
+                Box<[u8]>,
+                Vec<Box<[u8]>>,
+                Vec<SignatureE<Box<[u8]>, [u8; 64]>>,
+                Vec<SignatureE<Box<[u8]>, [u8; 64]>>,
+            >,
+            SendTransactionResponse,
+        >,
+        Signer::Signature: Signature<Serialized = [u8; 64]>,
+        Arguments: AsRef<[Argument]>,
+        Argument: AsRef<[u8]>,
+    {
+        let mut last_id = Vec::new();
+
+        for attempt in 1..=options.max_attempts {
+            if attempt > 1 {
+                send_options.reference_block = None;
+                send_options.sequence_number = None;
+                self.resync_sequence_number();
+            }
+
+            last_id = self
+                .send_transaction_header_with(transaction, send_options.clone())
+                .await?
+                .id;
+
+            let mut stream = StatusStream::new(&last_id, &mut self.client)
+                .delay(options.delay)
+                .timeout(options.timeout);
+
+            let mut reached = false;
+            let mut error = None;
+            while let Some(status) = stream.next().await {
+                match status {
+                    Ok(status) if status.reaches(options.target) => {
+                        reached = true;
+                        break;
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        error = Some(e);
+                        break;
+                    }
+                }
+            }
+            let last_status = stream.last_response().map(|response| response.status);
+            let result = stream.into_last_response();
+
+            if let Some(result) = &result {
+                self.update_sequence_number(result);
+            }
+
+            let dropped = match error {
+                None if reached => {
+                    return Ok(Resubmitted {
+                        id: last_id,
+                        attempts: attempt,
+                        result: result.expect("a status was received"),
+                    })
+                }
+                // The stream ended on a final status before reaching the target.
+                None => last_status == Some(TransactionStatus::Expired),
+                Some(TransactionStatusError::Failed {
+                    status_code,
+                    error_message,
+                }) => {
+                    if !is_proposal_key_error(&error_message) {
+                        return Err(ResubmitError::Failed {
+                            id: last_id,
+                            status_code,
+                            error_message,
+                        });
+                    }
+                    true
+                }
+                // The transaction could still be executed.
+                Some(TransactionStatusError::Timeout) => false,
+                Some(TransactionStatusError::Custom(e)) => {
+                    if !is_not_found(&*e) {
+                        return Err(ResubmitError::Custom(e));
+                    }
+                    true
+                }
+                Some(e) => return Err(ResubmitError::Custom(e.into())),
+            };
+
+            if !dropped {
+                return Err(ResubmitError::TimedOut { id: last_id });
+            }
+        }
+
+        Err(ResubmitError::AttemptsExhausted {
+            attempts: options.max_attempts,
+            last_id,
+        })
     }
 
     ///////////////
@@ -548,7 +1382,63 @@ where
         signer: &'a Signer,
         method: &'a SignMethod<SecretKey>,
     ) -> SignIter<'a, Signer> {
-        SignIter::new(hasher.finalize(), signer, method)
+        SignIter::new(hasher.finalize_digest(), signer, method)
+    }
+
+    /// Returns the sequence number to propose the next transaction with, using the tracked
+    /// sequence number for the primary key if it is known.
+    async fn next_sequence_number(&mut self, key_id: u32) -> Result<u64, SendTransactionError>
+    where
+        Client: for<'b> GrpcClient<GetAccountAtLatestBlockRequest<&'b [u8]>, AccountResponse>,
+    {
+        let tracked = key_id == self.primary_key_id();
+
+        if let (true, TrackedSequenceNumber::Known(sequence_number)) =
+            (tracked, self.sequence_number)
+        {
+            return Ok(sequence_number.into());
+        }
+
+        let acc = self
+            .client
+            .account_at_latest_block(&*self.address)
+            .await
+            .map_err(Into::into)?;
+
+        let sequence_number = acc
+            .keys
+            .iter()
+            .find(|key| key.index == key_id)
+            .map(|key| key.sequence_number)
+            .ok_or(SendTransactionError::ProposalKeyNotFound(key_id))?;
+
+        if tracked && self.sequence_number == TrackedSequenceNumber::Unknown {
+            self.sequence_number = TrackedSequenceNumber::Known(sequence_number);
+        }
+
+        Ok(sequence_number.into())
+    }
+
+    /// Updates the tracked sequence number after submitting a transaction proposed with the
+    /// primary key and its tracked sequence number.
+    ///
+    /// The sequence number is incremented if the transaction was accepted, and is left unchanged
+    /// if it was rejected, unless the node rejected it because of its proposal key.
+    fn submitted(
+        &mut self,
+        result: Result<SendTransactionResponse, Box<dyn StdError + Send + Sync>>,
+    ) -> Result<SendTransactionResponse, SendTransactionError> {
+        match (&result, self.sequence_number) {
+            (Ok(_), TrackedSequenceNumber::Known(sequence_number)) => {
+                self.sequence_number = TrackedSequenceNumber::Known(sequence_number + 1);
+            }
+            (Err(e), TrackedSequenceNumber::Known(_)) if is_proposal_key_rejection(&**e) => {
+                self.sequence_number = TrackedSequenceNumber::Unknown;
+            }
+            _ => {}
+        }
+
+        Ok(result.map_err(BoxError::from)?)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -584,99 +1474,57 @@ where
 
         Self::sign_(hasher, signer, method)
     }
-
-    #[allow(clippy::too_many_arguments)]
-    fn sign_transaction_header_<'a, 'b, Arguments>(
-        key_id: u32,
-        address: &[u8],
-        signer: &'a Signer,
-        method: &'a SignMethod<SecretKey>,
-        header: &'b TransactionHeader<Arguments>,
-        reference_block_id: impl AsRef<[u8]>,
-        sequence_number: u64,
-        gas_limit: u64,
-    ) -> SignIter<'a, Signer>
-    where
-        &'b Arguments: IntoIterator,
-        <&'b Arguments as IntoIterator>::IntoIter: ExactSizeIterator,
-        <<&'b Arguments as IntoIterator>::IntoIter as Iterator>::Item: AsRef<[u8]>,
-    {
-        Self::sign_transaction_(
-            key_id,
-            address,
-            signer,
-            method,
-            &header.script.as_ref(),
-            &header.arguments,
-            reference_block_id,
-            sequence_number,
-            gas_limit,
-        )
-    }
 }
 
-#[repr(transparent)]
-#[doc(hidden)] // implementation details
-pub struct SliceHelper<Item>([Item]);
-
-impl<Item> SliceHelper<Item> {
-    #[doc(hidden)]
-    pub fn new_ref(t: &[Item]) -> &Self {
-        unsafe { &*(t as *const [Item] as *const Self) }
+impl<Client, SecretKey, Signer, Hasher> PartySigner for Account<Client, SecretKey, Signer, Hasher>
+where
+    Signer: AsyncFlowSigner<SecretKey = SecretKey>,
+    Signer::Signature: Signature<Serialized = [u8; 64]>,
+    Hasher: FlowHasher,
+{
+    #[inline]
+    fn address(&self) -> &[u8] {
+        &self.address
     }
-}
-
-impl<'a, 'b, Item: 'a> IntoIterator for &'a &'b SliceHelper<Item> {
-    type Item = &'a Item;
-
-    type IntoIter = slice::Iter<'a, Item>;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.0.as_ref().iter()
+    fn sign_payload<'a>(&'a self, party: &'a mut SigningParty) -> PartySignFuture<'a, ()> {
+        Box::pin(async move { Ok(self.sign_party_async(party).await?) })
     }
-}
-
-impl<Item> otopr::HasItem for &'_ SliceHelper<Item> {
-    type Item = Item;
-}
 
-#[doc(hidden)] // implementation details
-#[derive(Clone)]
-pub struct EmitRefAndDropOnNextIter<'a, T, I>(Option<T>, I, PhantomData<&'a T>);
-
-#[doc(hidden)]
-#[derive(Clone)]
-pub struct EmitRefAndDropOnNext<T, I>(I, PhantomData<T>);
-
-impl<'a, T, I: Iterator<Item = T> + Clone> IntoIterator for &'a EmitRefAndDropOnNext<T, I> {
-    type Item = &'a T;
-
-    type IntoIter = EmitRefAndDropOnNextIter<'a, T, I>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        EmitRefAndDropOnNextIter(None, self.0.clone(), PhantomData)
+    fn sign_envelope<'a>(
+        &'a self,
+        party: &'a SigningParty,
+    ) -> PartySignFuture<'a, Vec<SignatureE<Box<[u8]>, [u8; 64]>>> {
+        Box::pin(async move {
+            let signatures = self.sign_async(Party::<Hasher>::envelope(party)).await?;
+            let key_ids = self.sign_method.key_ids();
+
+            Ok(signatures
+                .into_iter()
+                .zip(key_ids)
+                .map(|(sig, key_id)| SignatureE {
+                    address: self.address.clone(),
+                    key_id,
+                    signature: sig.serialize(),
+                })
+                .collect())
+        })
     }
 }
 
-impl<'a, T, I: Iterator<Item = T>> Iterator for EmitRefAndDropOnNextIter<'a, T, I> {
-    type Item = &'a T;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(it) = self.1.next() {
-            let r = self.0.insert(it);
-
-            // SAFETY: Must ensure that each item is dropped before calling `next()`.
-            //
-            // FIXME: Can we avoid this and work with the trait system instead?
-            Some(unsafe { &*(r as *const T) })
-        } else {
-            None
-        }
+/// Whether the access node reported that it does not know about a transaction, with a
+/// `NotFound` status.
+fn is_not_found(error: &(dyn StdError + 'static)) -> bool {
+    if let Some(status) = error.downcast_ref::<tonic::Status>() {
+        return status.code() == tonic::Code::NotFound;
     }
-}
 
-impl<T, I> otopr::HasItem for EmitRefAndDropOnNext<T, I> {
-    type Item = T;
+    // Transparent errors do not report the status as their source.
+    match error.downcast_ref::<TonicError>() {
+        Some(TonicError::Status(status)) => status.code() == tonic::Code::NotFound,
+        Some(TonicError::Custom(e)) => is_not_found(&**e),
+        None => error.source().is_some_and(is_not_found),
+    }
 }
 
 #[cfg(test)]
@@ -9,7 +9,27 @@ pub type DefaultSigner = DefaultSignerNoDoc;
 /// The default secret key, the exact type depends on the feature flags enabled.
 pub type DefaultSecretKey = DefaultSecretKeyNoDoc;
 
+use std::convert::Infallible;
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+
+use futures_util::future::ready;
+
+use crate::entities::AccountKey;
+use crate::error::VerifyError;
+
 include!("algorithms/macro_impl.rs");
+
+#[cfg(feature = "any-algorithms")]
+mod any;
+#[cfg(feature = "any-algorithms")]
+pub use any::*;
+
+#[cfg(feature = "remote-sign")]
+mod remote;
+#[cfg(feature = "remote-sign")]
+pub use remote::*;
 macro_rules! algorithms {
     ($($tt:tt)+) => {
         algorithms_impl!($($tt)+);
@@ -22,8 +42,18 @@ algorithms! {
         /// SHA2 256 bit hashing.
         Sha2 = (1, "SHA2_256"),
 
+        /// SHA2 384 bit hashing.
+        ///
+        /// Account keys cannot use this algorithm, since signing requires 256 bit hashes.
+        Sha2_384 = (2, "SHA2_384"),
+
         /// SHA3 256 bit hashing.
         Sha3 = (3, "SHA3_256"),
+
+        /// SHA3 384 bit hashing.
+        ///
+        /// Account keys cannot use this algorithm, since signing requires 256 bit hashes.
+        Sha3_384 = (4, "SHA3_384"),
     }
 
     /// A signature algorithm.
@@ -36,6 +66,74 @@ algorithms! {
     }
 }
 
+/// Marker for algorithms that are selected at runtime, such as the ones used by `AnySigner` and
+/// `AnyHasher`, behind the `any-algorithms` feature.
+///
+/// The code of this algorithm is `0`, which Flow uses for unknown algorithms.
+pub struct Dynamic;
+
+impl private::Sealed for Dynamic {}
+
+impl HashAlgorithm for Dynamic {
+    const CODE: u32 = 0;
+    const NAME: &'static str = "UNKNOWN";
+}
+
+impl SignatureAlgorithm for Dynamic {
+    const CODE: u32 = 0;
+    const NAME: &'static str = "UNKNOWN";
+}
+
+/// Hashed data, ready to be signed.
+///
+/// Hashers with a fixed algorithm produce one hash. Hashers that select their algorithm at
+/// runtime produce a hash for each supported algorithm, so that keys with different hashing
+/// algorithms can sign the same data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Digest {
+    /// A hash computed by a hasher with a fixed algorithm.
+    One([u8; 32]),
+
+    /// Hashes computed with both SHA2-256 and SHA3-256.
+    Both {
+        /// The SHA2-256 hash.
+        sha2: [u8; 32],
+        /// The SHA3-256 hash.
+        sha3: [u8; 32],
+    },
+}
+
+impl Digest {
+    /// Returns the hash to be signed by signers that do not select hashing algorithms at runtime.
+    ///
+    /// This is the SHA3-256 hash if both hashes are present.
+    pub fn primary(&self) -> [u8; 32] {
+        match *self {
+            Self::One(hash) => hash,
+            Self::Both { sha3, .. } => sha3,
+        }
+    }
+
+    /// Returns the hash computed with the hashing algorithm specified by its code.
+    ///
+    /// If this contains only one hash, it is returned regardless of the code.
+    pub fn get(&self, hash_algo: u32) -> Option<[u8; 32]> {
+        match *self {
+            Self::One(hash) => Some(hash),
+            Self::Both { sha2, .. } if hash_algo == Sha2::CODE => Some(sha2),
+            Self::Both { sha3, .. } if hash_algo == Sha3::CODE => Some(sha3),
+            Self::Both { .. } => None,
+        }
+    }
+}
+
+impl From<[u8; 32]> for Digest {
+    #[inline]
+    fn from(hash: [u8; 32]) -> Self {
+        Self::One(hash)
+    }
+}
+
 /// A signature.
 pub trait Signature {
     /// Serialized form of the signature
@@ -58,6 +156,19 @@ pub trait FlowHasher {
 
     /// Finalize the hasher, returns the 256 bit hash.
     fn finalize(self) -> [u8; 32];
+
+    /// Finalize the hasher, returns the hash(es) to be signed.
+    fn finalize_digest(self) -> Digest
+    where
+        Self: Sized,
+    {
+        Digest::One(self.finalize())
+    }
+
+    /// Returns whether this hasher can hash for a key with the hashing algorithm specified by its code.
+    fn is_compatible(hash_algo: u32) -> bool {
+        Self::Algorithm::CODE == hash_algo
+    }
 }
 
 /// A signature signer.
@@ -79,12 +190,30 @@ pub trait FlowSigner {
 
     /// Creates a signature by consuming a populated hasher and a secret key.
     fn sign(&self, hasher: impl FlowHasher, secret_key: &Self::SecretKey) -> Self::Signature {
-        self.sign_populated(hasher.finalize(), secret_key)
+        self.sign_digest(hasher.finalize_digest(), secret_key)
     }
 
     /// Signs a 256 bit hashed data with the secret key.
     fn sign_populated(&self, hashed: [u8; 32], secret_key: &Self::SecretKey) -> Self::Signature;
 
+    /// Signs hashed data with the secret key, choosing the hash that the secret key uses.
+    fn sign_digest(&self, digest: Digest, secret_key: &Self::SecretKey) -> Self::Signature {
+        self.sign_populated(digest.primary(), secret_key)
+    }
+
+    /// Returns whether the secret key can sign for an account key with the signature and hashing
+    /// algorithms specified by their codes.
+    ///
+    /// The hashing algorithm is checked by the hasher, see [`FlowHasher::is_compatible`].
+    fn is_compatible(
+        &self,
+        _secret_key: &Self::SecretKey,
+        sign_algo: u32,
+        _hash_algo: u32,
+    ) -> bool {
+        Self::Algorithm::CODE == sign_algo
+    }
+
     /// Converts a secret key to its public counterpart.
     fn to_public_key(&self, secret_key: &Self::SecretKey) -> Self::PublicKey;
 
@@ -92,6 +221,144 @@ pub trait FlowSigner {
     fn serialize_public_key(&self, public_key: &Self::PublicKey) -> [u8; 64];
 }
 
+/// The future returned by [`AsyncFlowSigner::sign_digest_async`].
+pub type SignFuture<'a, Signature, Error> =
+    Pin<Box<dyn Future<Output = Result<Signature, Error>> + 'a>>;
+
+/// A signer that signs asynchronously, for keys that are not held in process memory, such as keys
+/// in a KMS or an HSM.
+///
+/// Every [`FlowSigner`] is an asynchronous signer that never fails.
+pub trait AsyncFlowSigner {
+    /// The secret key used by this signer. This can be a handle to a key held elsewhere.
+    type SecretKey: Clone;
+
+    /// The signature type produced by this signer.
+    type Signature: Signature;
+
+    /// The error that could happen when signing.
+    type Error: Error + Send + Sync + 'static;
+
+    /// Creates a new signer.
+    ///
+    /// This is named differently from [`FlowSigner::new`] so that calls to it are not ambiguous
+    /// when both traits are in scope.
+    fn new_signer() -> Self;
+
+    /// Returns the serialized public key of a secret key. Excluding the leading 0x04.
+    fn public_key_bytes(&self, secret_key: &Self::SecretKey) -> [u8; 64];
+
+    /// Returns whether the secret key can sign for an account key with the signature and hashing
+    /// algorithms specified by their codes.
+    fn is_compatible_key(
+        &self,
+        secret_key: &Self::SecretKey,
+        sign_algo: u32,
+        hash_algo: u32,
+    ) -> bool;
+
+    /// Signs hashed data with the secret key, choosing the hash that the secret key uses.
+    fn sign_digest_async<'a>(
+        &'a self,
+        digest: Digest,
+        secret_key: &'a Self::SecretKey,
+    ) -> SignFuture<'a, Self::Signature, Self::Error>;
+}
+
+impl<T: FlowSigner> AsyncFlowSigner for T {
+    type SecretKey = T::SecretKey;
+
+    type Signature = T::Signature;
+
+    type Error = Infallible;
+
+    #[inline]
+    fn new_signer() -> Self {
+        FlowSigner::new()
+    }
+
+    fn public_key_bytes(&self, secret_key: &Self::SecretKey) -> [u8; 64] {
+        self.serialize_public_key(&self.to_public_key(secret_key))
+    }
+
+    #[inline]
+    fn is_compatible_key(
+        &self,
+        secret_key: &Self::SecretKey,
+        sign_algo: u32,
+        hash_algo: u32,
+    ) -> bool {
+        FlowSigner::is_compatible(self, secret_key, sign_algo, hash_algo)
+    }
+
+    fn sign_digest_async<'a>(
+        &'a self,
+        digest: Digest,
+        secret_key: &'a Self::SecretKey,
+    ) -> SignFuture<'a, Self::Signature, Self::Error> {
+        Box::pin(ready(Ok(self.sign_digest(digest, secret_key))))
+    }
+}
+
+/// A signature verifier.
+pub trait FlowVerifier {
+    /// The algorithm of this verifier.
+    type Algorithm: SignatureAlgorithm;
+
+    /// Creates a new verifier.
+    fn new() -> Self;
+
+    /// Verifies a signature by consuming a populated hasher and a public key. Excluding the
+    /// leading 0x04.
+    fn verify(
+        &self,
+        hasher: impl FlowHasher,
+        signature: &[u8],
+        public_key: &[u8; 64],
+    ) -> Result<(), VerifyError> {
+        self.verify_populated(hasher.finalize(), signature, public_key)
+    }
+
+    /// Verifies a signature of 256 bit hashed data with the public key. Excluding the leading 0x04.
+    fn verify_populated(
+        &self,
+        hashed: [u8; 32],
+        signature: &[u8],
+        public_key: &[u8; 64],
+    ) -> Result<(), VerifyError>;
+}
+
+/// Verifies a signature made by an account key, consuming a populated hasher.
+///
+/// Fails if the key is revoked, or if the verifier or the hasher does not use the algorithms of the
+/// key.
+pub fn verify_account_key<Verifier: FlowVerifier, Hasher: FlowHasher>(
+    verifier: &Verifier,
+    account_key: &AccountKey,
+    hasher: Hasher,
+    signature: &[u8],
+) -> Result<(), VerifyError> {
+    if account_key.revoked {
+        return Err(VerifyError::KeyRevoked);
+    }
+
+    if Verifier::Algorithm::CODE != account_key.sign_algo
+        || !Hasher::is_compatible(account_key.hash_algo)
+    {
+        return Err(VerifyError::AlgoMismatch);
+    }
+
+    let public_key = (&*account_key.public_key)
+        .try_into()
+        .map_err(|_| VerifyError::MalformedPublicKey)?;
+    let hashed = hasher
+        .finalize_digest()
+        .get(account_key.hash_algo)
+        .ok_or(VerifyError::AlgoMismatch)?;
+
+    verifier.verify_populated(hashed, signature, public_key)
+}
+
 /// A secret key.
 pub trait SecretKey {
     /// The signer associated to this secret key.
@@ -104,6 +371,18 @@ pub mod secp256k1 {
     pub use secp256k1::*;
 }
 
+#[cfg(feature = "p256-sign")]
+/// Re-exports items from the `p256` crate.
+pub mod p256 {
+    pub use p256::*;
+}
+
+#[cfg(feature = "sha2-hash")]
+/// Re-exports items from the `sha2` crate.
+pub mod sha2 {
+    pub use sha2::*;
+}
+
 #[cfg(feature = "sha3-hash")]
 /// Re-exports items from the `tiny_keccak` crate.
 pub mod sha3 {
@@ -136,6 +415,23 @@ impl FlowHasher for tiny_keccak::Sha3 {
     }
 }
 
+#[cfg(feature = "sha2-hash")]
+impl FlowHasher for sha2::Sha256 {
+    type Algorithm = Sha2;
+
+    fn new() -> Self {
+        sha2::Digest::new()
+    }
+
+    fn update<B: AsRef<[u8]> + ?Sized>(&mut self, bytes: &B) {
+        sha2::Digest::update(self, bytes.as_ref())
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        sha2::Digest::finalize(self).into()
+    }
+}
+
 #[cfg(feature = "secp256k1-sign")]
 impl Signature for secp256k1::Signature {
     type Serialized = [u8; 64];
@@ -184,6 +480,139 @@ impl SecretKey for secp256k1::SecretKey {
     type Signer = secp256k1::Secp256k1<secp256k1::SignOnly>;
 }
 
+/// The P-256 (secp256r1) signer.
+#[cfg(feature = "p256-sign")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct P256Signer;
+
+/// A P-256 public key in Flow's raw format: the X and Y coordinates, 32 bytes each, big-endian.
+#[cfg(feature = "p256-sign")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct P256PublicKey(pub [u8; 64]);
+
+#[cfg(feature = "p256-sign")]
+impl From<&p256::ecdsa::VerifyingKey> for P256PublicKey {
+    fn from(key: &p256::ecdsa::VerifyingKey) -> Self {
+        let point = key.to_encoded_point(false);
+        let mut raw = [0; 64];
+        // Excluding the leading 0x04.
+        raw.copy_from_slice(&point.as_bytes()[1..]);
+        Self(raw)
+    }
+}
+
+#[cfg(feature = "p256-sign")]
+impl Signature for p256::ecdsa::Signature {
+    type Serialized = [u8; 64];
+
+    fn serialize(&self) -> Self::Serialized {
+        let mut serialized = [0; 64];
+        serialized.copy_from_slice(&self.to_bytes());
+        serialized
+    }
+}
+
+#[cfg(feature = "p256-sign")]
+impl FlowSigner for P256Signer {
+    type Algorithm = P256;
+
+    type PublicKey = P256PublicKey;
+
+    type SecretKey = p256::ecdsa::SigningKey;
+
+    type Signature = p256::ecdsa::Signature;
+
+    fn new() -> Self {
+        Self
+    }
+
+    fn sign_populated(&self, hashed: [u8; 32], secret_key: &Self::SecretKey) -> Self::Signature {
+        use p256::ecdsa::signature::hazmat::PrehashSigner;
+        secret_key
+            .sign_prehash(&hashed)
+            .expect("signing a 256 bit hash should never fail")
+    }
+
+    fn to_public_key(&self, secret_key: &Self::SecretKey) -> Self::PublicKey {
+        secret_key.verifying_key().into()
+    }
+
+    fn serialize_public_key(&self, public_key: &Self::PublicKey) -> [u8; 64] {
+        public_key.0
+    }
+}
+
+#[cfg(feature = "p256-sign")]
+impl SecretKey for p256::ecdsa::SigningKey {
+    type Signer = P256Signer;
+}
+
+#[cfg(feature = "secp256k1-sign")]
+impl FlowVerifier for secp256k1::Secp256k1<secp256k1::VerifyOnly> {
+    type Algorithm = Secp256k1;
+
+    fn new() -> Self {
+        Self::verification_only()
+    }
+
+    fn verify_populated(
+        &self,
+        hashed: [u8; 32],
+        signature: &[u8],
+        public_key: &[u8; 64],
+    ) -> Result<(), VerifyError> {
+        let mut signature = secp256k1::Signature::from_compact(signature)
+            .map_err(|_| VerifyError::MalformedSignature)?;
+        // libsecp256k1 only accepts signatures with a low S value.
+        signature.normalize_s();
+
+        let mut uncompressed = [0x04; 65];
+        uncompressed[1..].copy_from_slice(public_key);
+        let public_key = secp256k1::PublicKey::from_slice(&uncompressed)
+            .map_err(|_| VerifyError::MalformedPublicKey)?;
+
+        let message = secp256k1::Message::from_slice(&hashed).expect("hash is 32 bytes");
+
+        self.verify(&message, &signature, &public_key)
+            .map_err(|_| VerifyError::InvalidSignature)
+    }
+}
+
+/// The P-256 (secp256r1) verifier.
+#[cfg(feature = "p256-sign")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct P256Verifier;
+
+#[cfg(feature = "p256-sign")]
+impl FlowVerifier for P256Verifier {
+    type Algorithm = P256;
+
+    fn new() -> Self {
+        Self
+    }
+
+    fn verify_populated(
+        &self,
+        hashed: [u8; 32],
+        signature: &[u8],
+        public_key: &[u8; 64],
+    ) -> Result<(), VerifyError> {
+        use p256::ecdsa::signature::hazmat::PrehashVerifier;
+
+        let signature = p256::ecdsa::Signature::from_slice(signature)
+            .map_err(|_| VerifyError::MalformedSignature)?;
+
+        let mut uncompressed = [0x04; 65];
+        uncompressed[1..].copy_from_slice(public_key);
+        let public_key = p256::ecdsa::VerifyingKey::from_sec1_bytes(&uncompressed)
+            .map_err(|_| VerifyError::MalformedPublicKey)?;
+
+        public_key
+            .verify_prehash(&hashed, &signature)
+            .map_err(|_| VerifyError::InvalidSignature)
+    }
+}
+
 #[cfg(feature = "sha3-hash")]
 type DefaultHasherNoDoc = tiny_keccak::Sha3;
 
@@ -0,0 +1,332 @@
+use std::slice;
+
+use zeroize::{Zeroize, Zeroizing};
+
+use super::*;
+use crate::entities::AccountKey;
+use crate::error::SecretKeyError;
+
+/// The hashing algorithms that can be selected at runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnyHashAlgorithm {
+    /// SHA2 256 bit hashing.
+    Sha2,
+    /// SHA3 256 bit hashing.
+    Sha3,
+}
+
+impl AnyHashAlgorithm {
+    /// Returns the hashing algorithm specified by its code.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            Sha2::CODE => Some(Self::Sha2),
+            Sha3::CODE => Some(Self::Sha3),
+            _ => None,
+        }
+    }
+
+    /// Returns the code of this hashing algorithm.
+    pub fn code(self) -> u32 {
+        match self {
+            Self::Sha2 => Sha2::CODE,
+            Self::Sha3 => Sha3::CODE,
+        }
+    }
+
+    /// Returns the hashing algorithm specified by its name, such as `SHA3_256`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            Sha2::NAME => Some(Self::Sha2),
+            Sha3::NAME => Some(Self::Sha3),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of this hashing algorithm.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sha2 => Sha2::NAME,
+            Self::Sha3 => Sha3::NAME,
+        }
+    }
+}
+
+/// The signature algorithms that can be selected at runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnySignatureAlgorithm {
+    /// ECDSA with the P-256 curve.
+    P256,
+    /// ECDSA with the secp256k1 curve.
+    Secp256k1,
+}
+
+impl AnySignatureAlgorithm {
+    /// Returns the signature algorithm specified by its code.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            P256::CODE => Some(Self::P256),
+            Secp256k1::CODE => Some(Self::Secp256k1),
+            _ => None,
+        }
+    }
+
+    /// Returns the code of this signature algorithm.
+    pub fn code(self) -> u32 {
+        match self {
+            Self::P256 => P256::CODE,
+            Self::Secp256k1 => Secp256k1::CODE,
+        }
+    }
+
+    /// Returns the signature algorithm specified by its name, such as `ECDSA_P256`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            P256::NAME => Some(Self::P256),
+            Secp256k1::NAME => Some(Self::Secp256k1),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of this signature algorithm.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::P256 => P256::NAME,
+            Self::Secp256k1 => Secp256k1::NAME,
+        }
+    }
+}
+
+#[derive(Clone)]
+enum AnyKey {
+    Secp256k1(secp256k1::SecretKey),
+    P256(p256::ecdsa::SigningKey),
+}
+
+/// A secret key of any supported signature algorithm, along with the hashing algorithm it uses.
+///
+/// Use this with [`AnySigner`] and [`AnyHasher`] to sign with keys whose algorithms are only
+/// known at runtime, or to log in to an account with keys of different algorithms.
+///
+/// The memory of the secret key is zeroized on drop.
+#[derive(Clone)]
+pub struct AnySecretKey {
+    key: AnyKey,
+    hash_algo: AnyHashAlgorithm,
+}
+
+impl AnySecretKey {
+    /// Creates a secret key from raw bytes, with the signature and hashing algorithms specified
+    /// by their codes.
+    pub fn new(sign_algo: u32, hash_algo: u32, secret_key: &[u8]) -> Result<Self, SecretKeyError> {
+        let hash_algo = AnyHashAlgorithm::from_code(hash_algo)
+            .ok_or(SecretKeyError::UnsupportedHashAlgorithm(hash_algo))?;
+
+        let key = match sign_algo {
+            Secp256k1::CODE => secp256k1::SecretKey::from_slice(secret_key)
+                .map(AnyKey::Secp256k1)
+                .map_err(|_| SecretKeyError::InvalidSecretKey)?,
+            P256::CODE => p256::ecdsa::SigningKey::from_slice(secret_key)
+                .map(AnyKey::P256)
+                .map_err(|_| SecretKeyError::InvalidSecretKey)?,
+            _ => return Err(SecretKeyError::UnsupportedSignatureAlgorithm(sign_algo)),
+        };
+
+        Ok(Self { key, hash_algo })
+    }
+
+    /// Creates a secret key from raw bytes, using the algorithms of an account key.
+    pub fn for_account_key(
+        account_key: &AccountKey,
+        secret_key: &[u8],
+    ) -> Result<Self, SecretKeyError> {
+        Self::new(account_key.sign_algo, account_key.hash_algo, secret_key)
+    }
+
+    /// Creates a secp256k1 secret key that uses the specified hashing algorithm.
+    pub fn secp256k1(secret_key: secp256k1::SecretKey, hash_algo: AnyHashAlgorithm) -> Self {
+        Self {
+            key: AnyKey::Secp256k1(secret_key),
+            hash_algo,
+        }
+    }
+
+    /// Creates a P-256 secret key that uses the specified hashing algorithm.
+    pub fn p256(secret_key: p256::ecdsa::SigningKey, hash_algo: AnyHashAlgorithm) -> Self {
+        Self {
+            key: AnyKey::P256(secret_key),
+            hash_algo,
+        }
+    }
+
+    /// Returns the code of the signature algorithm of this key.
+    pub fn sign_algo(&self) -> u32 {
+        match self.key {
+            AnyKey::Secp256k1(_) => Secp256k1::CODE,
+            AnyKey::P256(_) => P256::CODE,
+        }
+    }
+
+    /// Returns the raw bytes of this secret key.
+    pub fn to_bytes(&self) -> Zeroizing<[u8; 32]> {
+        let mut bytes = Zeroizing::new([0; 32]);
+        match &self.key {
+            AnyKey::Secp256k1(sk) => bytes.copy_from_slice(&sk[..]),
+            AnyKey::P256(sk) => bytes.copy_from_slice(&sk.to_bytes()),
+        }
+        bytes
+    }
+
+    /// Returns the hashing algorithm this key uses.
+    pub fn hash_algo(&self) -> AnyHashAlgorithm {
+        self.hash_algo
+    }
+}
+
+impl Drop for AnySecretKey {
+    fn drop(&mut self) {
+        // `p256::ecdsa::SigningKey` zeroizes itself on drop.
+        if let AnyKey::Secp256k1(sk) = &mut self.key {
+            // SAFETY: the pointer is valid for `sk.len()` bytes. The key is never used afterwards.
+            unsafe { slice::from_raw_parts_mut(sk.as_mut_ptr(), sk.len()) }.zeroize();
+        }
+    }
+}
+
+/// A public key of any supported signature algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnyPublicKey {
+    /// A secp256k1 public key.
+    Secp256k1(secp256k1::PublicKey),
+    /// A P-256 public key.
+    P256(P256PublicKey),
+}
+
+/// A signature of any supported signature algorithm.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnySignature {
+    /// A secp256k1 signature.
+    Secp256k1(secp256k1::Signature),
+    /// A P-256 signature.
+    P256(p256::ecdsa::Signature),
+}
+
+impl Signature for AnySignature {
+    type Serialized = [u8; 64];
+
+    fn serialize(&self) -> Self::Serialized {
+        match self {
+            Self::Secp256k1(sig) => sig.serialize(),
+            Self::P256(sig) => sig.serialize(),
+        }
+    }
+}
+
+/// A signer that dispatches to the signer of the secret key's algorithm.
+///
+/// The signature and hashing algorithms are selected at runtime by [`AnySecretKey`], so this
+/// should be used together with [`AnyHasher`]. Templates that need the algorithm names at compile
+/// time, such as [`CreateAccountTransaction`], cannot be used with this signer.
+///
+/// [`CreateAccountTransaction`]: crate::transaction::CreateAccountTransaction
+#[derive(Clone)]
+pub struct AnySigner {
+    secp256k1: secp256k1::Secp256k1<secp256k1::SignOnly>,
+    p256: P256Signer,
+}
+
+impl FlowSigner for AnySigner {
+    type Algorithm = Dynamic;
+
+    type SecretKey = AnySecretKey;
+
+    type PublicKey = AnyPublicKey;
+
+    type Signature = AnySignature;
+
+    fn new() -> Self {
+        Self {
+            secp256k1: FlowSigner::new(),
+            p256: P256Signer,
+        }
+    }
+
+    fn sign_populated(&self, hashed: [u8; 32], secret_key: &Self::SecretKey) -> Self::Signature {
+        match &secret_key.key {
+            AnyKey::Secp256k1(sk) => {
+                AnySignature::Secp256k1(self.secp256k1.sign_populated(hashed, sk))
+            }
+            AnyKey::P256(sk) => AnySignature::P256(self.p256.sign_populated(hashed, sk)),
+        }
+    }
+
+    fn sign_digest(&self, digest: Digest, secret_key: &Self::SecretKey) -> Self::Signature {
+        let hashed = digest
+            .get(secret_key.hash_algo.code())
+            .expect("digests contain hashes of all algorithms supported by AnySecretKey");
+        self.sign_populated(hashed, secret_key)
+    }
+
+    fn to_public_key(&self, secret_key: &Self::SecretKey) -> Self::PublicKey {
+        match &secret_key.key {
+            AnyKey::Secp256k1(sk) => AnyPublicKey::Secp256k1(self.secp256k1.to_public_key(sk)),
+            AnyKey::P256(sk) => AnyPublicKey::P256(self.p256.to_public_key(sk)),
+        }
+    }
+
+    fn serialize_public_key(&self, public_key: &Self::PublicKey) -> [u8; 64] {
+        match public_key {
+            AnyPublicKey::Secp256k1(pk) => self.secp256k1.serialize_public_key(pk),
+            AnyPublicKey::P256(pk) => self.p256.serialize_public_key(pk),
+        }
+    }
+
+    fn is_compatible(&self, secret_key: &Self::SecretKey, sign_algo: u32, hash_algo: u32) -> bool {
+        secret_key.sign_algo() == sign_algo && secret_key.hash_algo.code() == hash_algo
+    }
+}
+
+impl SecretKey for AnySecretKey {
+    type Signer = AnySigner;
+}
+
+/// A hasher that computes both SHA2-256 and SHA3-256 hashes, so that the hash can be chosen by
+/// the secret key when signing.
+///
+/// [`FlowHasher::finalize`] returns the SHA3-256 hash, use [`FlowHasher::finalize_digest`] to
+/// obtain both.
+#[derive(Clone)]
+pub struct AnyHasher {
+    sha2: sha2::Sha256,
+    sha3: tiny_keccak::Sha3,
+}
+
+impl FlowHasher for AnyHasher {
+    type Algorithm = Dynamic;
+
+    fn new() -> Self {
+        Self {
+            sha2: FlowHasher::new(),
+            sha3: FlowHasher::new(),
+        }
+    }
+
+    fn update<B: AsRef<[u8]> + ?Sized>(&mut self, bytes: &B) {
+        FlowHasher::update(&mut self.sha2, bytes);
+        FlowHasher::update(&mut self.sha3, bytes);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        FlowHasher::finalize(self.sha3)
+    }
+
+    fn finalize_digest(self) -> Digest {
+        Digest::Both {
+            sha2: FlowHasher::finalize(self.sha2),
+            sha3: FlowHasher::finalize(self.sha3),
+        }
+    }
+
+    fn is_compatible(hash_algo: u32) -> bool {
+        AnyHashAlgorithm::from_code(hash_algo).is_some()
+    }
+}
@@ -0,0 +1,253 @@
+use hyper::client::HttpConnector;
+use hyper::{Body, Client, Method, Request, Uri};
+use serde_json::{json, Value};
+
+use super::*;
+use crate::error::RemoteSignerError;
+
+/// A handle to a key held by a remote signing service, along with the public information of the
+/// key.
+///
+/// The secret key never enters this process. See [`RemoteSigner`] for the protocol used to talk to
+/// the signing service.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteKey {
+    endpoint: Box<str>,
+    key_id: Box<str>,
+    public_key: [u8; 64],
+    sign_algo: u32,
+    hash_algo: u32,
+}
+
+impl RemoteKey {
+    /// Creates a handle to a key from its public information, without contacting the signing
+    /// service.
+    pub fn new(
+        endpoint: impl Into<Box<str>>,
+        key_id: impl Into<Box<str>>,
+        public_key: [u8; 64],
+        sign_algo: u32,
+        hash_algo: u32,
+    ) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            key_id: key_id.into(),
+            public_key,
+            sign_algo,
+            hash_algo,
+        }
+    }
+
+    /// Returns the base URI of the signing service holding this key.
+    #[inline]
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Returns the identifier of this key in the signing service.
+    #[inline]
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Returns the public key, excluding the leading 0x04.
+    #[inline]
+    pub fn public_key(&self) -> &[u8; 64] {
+        &self.public_key
+    }
+
+    /// Returns the code of the signature algorithm of this key.
+    #[inline]
+    pub fn sign_algo(&self) -> u32 {
+        self.sign_algo
+    }
+
+    /// Returns the code of the hashing algorithm of this key.
+    #[inline]
+    pub fn hash_algo(&self) -> u32 {
+        self.hash_algo
+    }
+}
+
+/// A signature created by a remote signing service.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RemoteSignature(pub [u8; 64]);
+
+impl Signature for RemoteSignature {
+    type Serialized = [u8; 64];
+
+    #[inline]
+    fn serialize(&self) -> Self::Serialized {
+        self.0
+    }
+}
+
+/// An asynchronous signer that delegates signing to a signing service over HTTP, such as a
+/// daemon fronting a KMS or an HSM.
+///
+/// The signing service must handle two JSON requests, relative to the endpoint of the key:
+///
+///  - `POST /public-key` with `{"key_id": "..."}`, responding with
+///    `{"public_key": "<hex>", "sign_algo": 2, "hash_algo": 1}`. The public key excludes the
+///    leading 0x04.
+///  - `POST /sign` with `{"key_id": "...", "digest": "<hex>"}`, responding with
+///    `{"signature": "<hex>"}`. The digest is the 32 byte hash to be signed, computed with the
+///    hashing algorithm of the key. The signature is `r` followed by `s`, 32 bytes each.
+///
+/// Any other status than `200 OK` is treated as an error.
+#[derive(Clone)]
+pub struct RemoteSigner {
+    client: Client<HttpConnector>,
+}
+
+impl RemoteSigner {
+    /// Creates a new signer with a default HTTP client.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_client(Client::new())
+    }
+
+    /// Creates a new signer with the HTTP client provided.
+    #[inline]
+    pub fn with_client(client: Client<HttpConnector>) -> Self {
+        Self { client }
+    }
+
+    /// Fetches the public information of a key from the signing service at `endpoint`.
+    pub async fn fetch_key(
+        &self,
+        endpoint: impl Into<Box<str>>,
+        key_id: impl Into<Box<str>>,
+    ) -> Result<RemoteKey, RemoteSignerError> {
+        let endpoint = endpoint.into();
+        let key_id = key_id.into();
+
+        let response = self
+            .post(&endpoint, "public-key", json!({ "key_id": &*key_id }))
+            .await?;
+
+        let public_key = hex_field(&response, "public_key")?;
+        let sign_algo = code_field(&response, "sign_algo")?;
+        let hash_algo = code_field(&response, "hash_algo")?;
+
+        Ok(RemoteKey {
+            endpoint,
+            key_id,
+            public_key,
+            sign_algo,
+            hash_algo,
+        })
+    }
+
+    async fn sign(
+        &self,
+        digest: Digest,
+        key: &RemoteKey,
+    ) -> Result<RemoteSignature, RemoteSignerError> {
+        let hashed = digest
+            .get(key.hash_algo)
+            .ok_or(RemoteSignerError::MissingHash(key.hash_algo))?;
+
+        let response = self
+            .post(
+                &key.endpoint,
+                "sign",
+                json!({ "key_id": &*key.key_id, "digest": hex::encode(hashed) }),
+            )
+            .await?;
+
+        hex_field(&response, "signature").map(RemoteSignature)
+    }
+
+    async fn post(
+        &self,
+        endpoint: &str,
+        path: &str,
+        body: Value,
+    ) -> Result<Value, RemoteSignerError> {
+        let uri: Uri = format!("{}/{}", endpoint.trim_end_matches('/'), path).parse()?;
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .expect("the request is valid");
+
+        let response = self.client.request(request).await?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+
+        if !status.is_success() {
+            return Err(RemoteSignerError::Status {
+                status: status.as_u16(),
+                message: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
+
+impl Default for RemoteSigner {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsyncFlowSigner for RemoteSigner {
+    type SecretKey = RemoteKey;
+
+    type Signature = RemoteSignature;
+
+    type Error = RemoteSignerError;
+
+    #[inline]
+    fn new_signer() -> Self {
+        Self::new()
+    }
+
+    #[inline]
+    fn public_key_bytes(&self, secret_key: &Self::SecretKey) -> [u8; 64] {
+        secret_key.public_key
+    }
+
+    fn is_compatible_key(
+        &self,
+        secret_key: &Self::SecretKey,
+        sign_algo: u32,
+        hash_algo: u32,
+    ) -> bool {
+        secret_key.sign_algo == sign_algo && secret_key.hash_algo == hash_algo
+    }
+
+    fn sign_digest_async<'a>(
+        &'a self,
+        digest: Digest,
+        secret_key: &'a Self::SecretKey,
+    ) -> SignFuture<'a, Self::Signature, Self::Error> {
+        Box::pin(self.sign(digest, secret_key))
+    }
+}
+
+fn hex_field<const N: usize>(
+    value: &Value,
+    field: &'static str,
+) -> Result<[u8; N], RemoteSignerError> {
+    let mut bytes = [0; N];
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .and_then(|s| hex::decode_to_slice(s, &mut bytes).ok())
+        .ok_or(RemoteSignerError::InvalidResponse(field))?;
+    Ok(bytes)
+}
+
+fn code_field(value: &Value, field: &'static str) -> Result<u32, RemoteSignerError> {
+    value
+        .get(field)
+        .and_then(Value::as_u64)
+        .and_then(|code| u32::try_from(code).ok())
+        .ok_or(RemoteSignerError::InvalidResponse(field))
+}
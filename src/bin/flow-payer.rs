@@ -0,0 +1,188 @@
+//! Serves a [`PayerService`] over HTTP.
+//!
+//! The payer is an account of `flow.json`. Parties exported with `PortableParty::export_json`
+//! are posted to `/sign`, and returned with the envelope signatures of the payer. Requests larger
+//! than 512 KiB are rejected.
+//!
+//! ```text
+//! flow-payer --account payer --network testnet --listen 127.0.0.1:8701 \
+//!     --max-gas-limit 1000 --allow-script transaction.cdc --rate-limit 10/60
+//! ```
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use flow_sdk::account::AnyAccount;
+use flow_sdk::client::TonicHyperFlowClient;
+use flow_sdk::config::Config;
+use flow_sdk::error::SigningSessionError;
+use flow_sdk::payer::{self, PayerPolicy, PayerService};
+use http_body::{LengthLimitError, Limited};
+use hyper::body::HttpBody;
+use hyper::rt::Executor;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use tokio::task::LocalSet;
+
+type Service = PayerService<AnyAccount<TonicHyperFlowClient>>;
+
+/// The maximum size of a request, in bytes.
+const MAX_BODY_SIZE: usize = 512 * 1024;
+
+const USAGE: &str = "\
+Usage: flow-payer [OPTIONS]
+
+Options:
+    --config <PATH>          The configuration file [default: flow.json]
+    --network <NAME>         The network to connect to [default: the default network]
+    --account <NAME>         The account paying the fees [default: payer]
+    --listen <ADDRESS>       The address to listen on [default: 127.0.0.1:8701]
+    --max-gas-limit <GAS>    The maximum gas limit of transactions [default: 9999]
+    --allow-script <PATH>    Allows the script in the file, can be repeated [default: any script]
+    --rate-limit <N>/<SECS>  Allows N transactions per proposer or authorizer every SECS seconds
+";
+
+struct Args {
+    config: String,
+    network: Option<String>,
+    account: String,
+    listen: SocketAddr,
+    policy: PayerPolicy,
+}
+
+fn parse_args() -> Result<Args, Box<dyn Error + Send + Sync>> {
+    let mut args = Args {
+        config: "flow.json".into(),
+        network: None,
+        account: "payer".into(),
+        listen: ([127, 0, 0, 1], 8701).into(),
+        policy: PayerPolicy::new(),
+    };
+
+    let mut iter = std::env::args().skip(1);
+    while let Some(flag) = iter.next() {
+        if flag == "--help" || flag == "-h" {
+            print!("{}", USAGE);
+            std::process::exit(0);
+        }
+
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+
+        match &*flag {
+            "--config" => args.config = value,
+            "--network" => args.network = Some(value),
+            "--account" => args.account = value,
+            "--listen" => args.listen = value.parse()?,
+            "--max-gas-limit" => args.policy = args.policy.max_gas_limit(value.parse()?),
+            "--allow-script" => {
+                let script = std::fs::read(&value)?;
+                args.policy = args.policy.allow_script(script);
+            }
+            "--rate-limit" => {
+                let (max, secs) = value
+                    .split_once('/')
+                    .ok_or("the rate limit must be formatted as <N>/<SECS>")?;
+                args.policy = args
+                    .policy
+                    .rate_limit(max.parse()?, Duration::from_secs(secs.parse()?));
+            }
+            _ => return Err(format!("unknown option {}\n\n{}", flag, USAGE).into()),
+        }
+    }
+
+    Ok(args)
+}
+
+async fn handle(
+    service: Arc<Service>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    if request.method() != Method::POST || request.uri().path() != "/sign" {
+        return Ok(respond(StatusCode::NOT_FOUND, "not found".into()));
+    }
+
+    if request.body().size_hint().lower() > MAX_BODY_SIZE as u64 {
+        return Ok(too_large());
+    }
+
+    let body = match hyper::body::to_bytes(Limited::new(request.into_body(), MAX_BODY_SIZE)).await {
+        Ok(body) => body,
+        Err(e) if e.is::<LengthLimitError>() => return Ok(too_large()),
+        Err(e) => return Ok(respond(StatusCode::BAD_REQUEST, e.to_string())),
+    };
+    let body = match std::str::from_utf8(&body) {
+        Ok(body) => body,
+        Err(e) => return Ok(respond(StatusCode::BAD_REQUEST, e.to_string())),
+    };
+
+    let mut client = service.payer().client_cloned();
+    Ok(match service.sign_json(&mut client, body).await {
+        Ok(json) => respond(StatusCode::OK, json),
+        Err(e @ payer::Error::Interchange(_)) => respond(StatusCode::BAD_REQUEST, e.to_string()),
+        Err(e @ payer::Error::Session(SigningSessionError::Custom(_))) => {
+            respond(StatusCode::BAD_GATEWAY, e.to_string())
+        }
+        Err(e) => respond(StatusCode::FORBIDDEN, e.to_string()),
+    })
+}
+
+/// Spawns the connections on the current thread.
+#[derive(Clone, Copy)]
+struct LocalExec;
+
+impl<F: Future + 'static> Executor<F> for LocalExec {
+    fn execute(&self, future: F) {
+        tokio::task::spawn_local(future);
+    }
+}
+
+fn too_large() -> Response<Body> {
+    respond(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        format!("the request must be at most {} bytes long", MAX_BODY_SIZE),
+    )
+}
+
+fn respond(status: StatusCode, body: String) -> Response<Body> {
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let args = parse_args()?;
+
+    let config = Config::load_from(&args.config)?;
+    let network = args
+        .network
+        .unwrap_or_else(|| config.default_network().to_owned());
+    let payer = config.account_on(&args.account, &network).await?;
+
+    let service = Arc::new(PayerService::new(payer, args.policy));
+
+    // Requests to the access node are not `Send`, so connections are served on this thread.
+    let server = Server::bind(&args.listen)
+        .executor(LocalExec)
+        .serve(make_service_fn(move |_| {
+            let service = service.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| handle(service.clone(), request)))
+            }
+        }));
+
+    println!(
+        "Paying for transactions on {} at http://{}/sign",
+        network,
+        server.local_addr()
+    );
+    LocalSet::new().run_until(server).await?;
+
+    Ok(())
+}
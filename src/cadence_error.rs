@@ -0,0 +1,386 @@
+//! Structured errors parsed from the error messages of failed transactions and scripts.
+//!
+//! Flow reports errors as text, such as:
+//!
+//! ```text
+//! [Error Code: 1101] cadence runtime error: Execution failed:
+//! error: pre-condition failed: Amount withdrawn must be less than or equal than the balance of the Vault
+//!    --> f233dcee88fe0abe.FungibleToken:194:16
+//! ```
+//!
+//! [`CadenceError::parse`] extracts the error code, the message and the location from them, so
+//! that errors such as running out of gas or failed pre-conditions can be handled.
+//!
+//! ```rust
+//! # use flow_sdk::cadence_error::{CadenceError, ErrorCode};
+//! let error = CadenceError::parse(
+//!     "[Error Code: 1101] cadence runtime error: Execution failed:\n\
+//!      error: pre-condition failed: not enough balance\n   --> f233dcee88fe0abe.FungibleToken:194:16",
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(Some(ErrorCode::CadenceRuntime), error.code);
+//! assert_eq!("pre-condition failed: not enough balance", error.message);
+//! assert_eq!(Some("f233dcee88fe0abe.FungibleToken"), error.location.as_deref());
+//! assert_eq!((Some(194), Some(16)), (error.line, error.column));
+//! assert!(error.is_precondition_failure());
+//! assert!(!error.is_retryable());
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::access::TransactionResultResponse;
+use crate::error::{TonicError, TransactionStatusError};
+
+const CODE_PREFIX: &str = "[Error Code: ";
+
+macro_rules! error_codes {
+    ($($(#[$meta:meta])* $name:ident = $code:literal,)*) => {
+        /// The codes of the errors reported by the Flow virtual machine.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum ErrorCode {
+            $($(#[$meta])* $name,)*
+
+            /// A code that is not known to this crate.
+            Other(u32),
+        }
+
+        impl ErrorCode {
+            /// Returns the error code with this number.
+            pub const fn from_code(code: u32) -> Self {
+                match code {
+                    $($code => Self::$name,)*
+                    code => Self::Other(code),
+                }
+            }
+
+            /// Returns the number of this error code.
+            pub const fn code(self) -> u32 {
+                match self {
+                    $(Self::$name => $code,)*
+                    Self::Other(code) => code,
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    /// The transaction is not valid.
+    TxValidation = 1000,
+    /// The transaction is too large.
+    InvalidTxByteSize = 1001,
+    /// The reference block of the transaction is not valid.
+    InvalidReferenceBlock = 1002,
+    /// The transaction expired.
+    ExpiredTransaction = 1003,
+    /// The script of the transaction is not valid.
+    InvalidScript = 1004,
+    /// The gas limit of the transaction is not valid.
+    InvalidGasLimit = 1005,
+    /// The signature of the proposal key is not valid.
+    InvalidProposalSignature = 1006,
+    /// The sequence number of the proposal key does not match.
+    InvalidProposalSeqNumber = 1007,
+    /// A payload signature is not valid.
+    InvalidPayloadSignature = 1008,
+    /// An envelope signature is not valid.
+    InvalidEnvelopeSignature = 1009,
+
+    /// An internal error of the virtual machine.
+    FvmInternal = 1050,
+    /// A value is not valid.
+    Value = 1051,
+    /// An argument is not valid.
+    InvalidArgument = 1052,
+    /// An address is not valid.
+    InvalidAddress = 1053,
+    /// A location is not valid.
+    InvalidLocation = 1054,
+    /// An account did not authorize the operation.
+    AccountAuthorization = 1055,
+    /// The operation is not authorized.
+    OperationAuthorization = 1056,
+    /// The operation is not supported.
+    OperationNotSupported = 1057,
+    /// The block height is out of range.
+    BlockHeightOutOfRange = 1058,
+
+    /// The execution failed.
+    Execution = 1100,
+    /// An error of the Cadence runtime, such as a failed pre-condition or a panic.
+    CadenceRuntime = 1101,
+    /// A value could not be encoded.
+    EncodingUnsupportedValue = 1102,
+    /// An account does not have enough storage capacity.
+    StorageCapacityExceeded = 1103,
+    /// The gas limit was exceeded.
+    GasLimitExceeded = 1104,
+    /// Too many events were emitted.
+    EventLimitExceeded = 1105,
+    /// Too many ledger interactions were made.
+    LedgerInteractionLimitExceeded = 1106,
+    /// A storage key is too large.
+    StateKeySizeLimit = 1107,
+    /// A storage value is too large.
+    StateValueSizeLimit = 1108,
+    /// The transaction fees could not be deducted.
+    TransactionFeeDeductionFailed = 1109,
+    /// The computation limit was exceeded.
+    ComputationLimitExceeded = 1110,
+    /// The memory limit was exceeded.
+    MemoryLimitExceeded = 1111,
+    /// The execution parameters could not be decoded.
+    CouldNotDecodeExecutionParameterFromState = 1112,
+    /// The script took too long to execute.
+    ScriptExecutionTimedOut = 1113,
+    /// The execution of the script was cancelled.
+    ScriptExecutionCancelled = 1114,
+    /// An event could not be encoded.
+    EventEncoding = 1115,
+    /// Internal state was accessed.
+    InvalidInternalStateAccess = 1116,
+    /// The payer does not have enough balance to pay for the transaction.
+    InsufficientPayerBalance = 1118,
+
+    /// An account error.
+    Account = 1200,
+    /// An account could not be found.
+    AccountNotFound = 1201,
+    /// A key of an account could not be found.
+    AccountPublicKeyNotFound = 1202,
+    /// The account already exists.
+    AccountAlreadyExists = 1203,
+    /// The account is frozen.
+    FrozenAccount = 1204,
+    /// The storage of the account is not initialized.
+    AccountStorageNotInitialized = 1205,
+    /// The account has too many keys.
+    AccountPublicKeyLimit = 1206,
+
+    /// A contract error.
+    Contract = 1250,
+    /// A contract could not be found.
+    ContractNotFound = 1251,
+    /// The contract names of an account could not be found.
+    ContractNamesNotFound = 1252,
+
+    /// An error of the EVM.
+    Evm = 1300,
+}
+
+impl ErrorCode {
+    /// Whether the transaction failed because of its proposal key. The sequence number of the key
+    /// is not incremented, and could be out of sync.
+    pub const fn is_proposal_key_error(self) -> bool {
+        matches!(
+            self,
+            Self::InvalidProposalSignature | Self::InvalidProposalSeqNumber
+        )
+    }
+
+    /// Whether the transaction or the script could succeed if it is sent again, signed with a
+    /// fresh reference block and sequence number.
+    pub const fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            Self::InvalidReferenceBlock
+                | Self::ExpiredTransaction
+                | Self::InvalidProposalSignature
+                | Self::InvalidProposalSeqNumber
+                | Self::ScriptExecutionTimedOut
+                | Self::ScriptExecutionCancelled
+        )
+    }
+}
+
+/// An error of a failed transaction or script, parsed from its error message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CadenceError {
+    /// The most specific error code in the message, if any.
+    pub code: Option<ErrorCode>,
+
+    /// The message of the error, without the error codes and the location.
+    pub message: String,
+
+    /// Where the error happened, such as a contract like `f233dcee88fe0abe.FungibleToken`, or
+    /// the ID of a transaction.
+    pub location: Option<String>,
+
+    /// The line where the error happened.
+    pub line: Option<u32>,
+
+    /// The column where the error happened.
+    pub column: Option<u32>,
+}
+
+impl CadenceError {
+    /// Parses an error message, returns `None` if it is empty.
+    pub fn parse(error_message: &str) -> Option<Self> {
+        if error_message.trim().is_empty() {
+            return None;
+        }
+
+        // Errors are wrapped, the innermost code is the last one.
+        let mut code = None;
+        let mut after_code = error_message;
+        let mut rest = error_message;
+        while let Some(start) = rest.find(CODE_PREFIX) {
+            rest = &rest[start + CODE_PREFIX.len()..];
+            let end = match rest.find(']') {
+                Some(end) => end,
+                None => break,
+            };
+
+            if let Ok(number) = rest[..end].trim().parse() {
+                code = Some(ErrorCode::from_code(number));
+                after_code = &rest[end + 1..];
+            }
+        }
+
+        // Cadence reports the error on its own line, followed by the location.
+        let lines = || after_code.lines().map(str::trim);
+        let message = lines()
+            .find_map(|line| line.strip_prefix("error: "))
+            .or_else(|| lines().find(|line| !line.is_empty()))
+            .unwrap_or_default()
+            .to_owned();
+
+        let (location, line, column) = match lines().find_map(|line| line.strip_prefix("--> ")) {
+            Some(location) => parse_location(location),
+            None => (None, None, None),
+        };
+
+        Some(Self {
+            code,
+            message,
+            location,
+            line,
+            column,
+        })
+    }
+
+    /// Finds and parses the error of a failed script, reported by the access node in a
+    /// [`tonic::Status`], in an error or its sources.
+    pub fn from_error(error: &(dyn Error + 'static)) -> Option<Self> {
+        let mut error = Some(error);
+
+        while let Some(e) = error {
+            if let Some(status) = e.downcast_ref::<tonic::Status>() {
+                return Self::parse(status.message()).filter(|error| error.code.is_some());
+            }
+            // Transparent errors do not report the status as their source.
+            if let Some(e) = e.downcast_ref::<TonicError>() {
+                return e.cadence_error();
+            }
+            error = e.source();
+        }
+
+        None
+    }
+
+    /// Whether the transaction or the script could succeed if it is sent again, see
+    /// [`ErrorCode::is_retryable`].
+    pub fn is_retryable(&self) -> bool {
+        self.code.is_some_and(ErrorCode::is_retryable)
+    }
+
+    /// Whether the transaction ran out of gas.
+    pub fn is_out_of_gas(&self) -> bool {
+        matches!(
+            self.code,
+            Some(ErrorCode::GasLimitExceeded | ErrorCode::ComputationLimitExceeded)
+        )
+    }
+
+    /// Whether an account does not have enough storage capacity.
+    pub fn is_storage_capacity_exceeded(&self) -> bool {
+        self.code == Some(ErrorCode::StorageCapacityExceeded)
+    }
+
+    /// Whether a pre-condition of a function or a transaction failed.
+    pub fn is_precondition_failure(&self) -> bool {
+        self.message.starts_with("pre-condition failed")
+    }
+}
+
+impl fmt::Display for CadenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(code) = self.code {
+            write!(f, "{}{}] ", CODE_PREFIX, code.code())?;
+        }
+
+        f.write_str(&self.message)?;
+
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location)?;
+
+            if let (Some(line), Some(column)) = (self.line, self.column) {
+                write!(f, ":{}:{}", line, column)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for CadenceError {}
+
+/// Splits a location such as `f233dcee88fe0abe.FungibleToken:194:16`.
+fn parse_location(location: &str) -> (Option<String>, Option<u32>, Option<u32>) {
+    let mut parts = location.rsplitn(3, ':');
+
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(column), Some(line), Some(location)) => match (line.parse(), column.parse()) {
+            (Ok(line), Ok(column)) => (Some(location.into()), Some(line), Some(column)),
+            _ => (Some(location.into()), None, None),
+        },
+        _ => (Some(location.into()), None, None),
+    }
+}
+
+/// Returns whether a transaction failed because of its proposal key, from its error message.
+pub(crate) fn is_proposal_key_error(error_message: &str) -> bool {
+    CadenceError::parse(error_message)
+        .and_then(|error| error.code)
+        .is_some_and(ErrorCode::is_proposal_key_error)
+}
+
+/// Returns whether the access node rejected a transaction because of its proposal key.
+pub(crate) fn is_proposal_key_rejection(error: &(dyn Error + 'static)) -> bool {
+    CadenceError::from_error(error)
+        .and_then(|error| error.code)
+        .is_some_and(ErrorCode::is_proposal_key_error)
+}
+
+impl TransactionResultResponse {
+    /// Parses the error of the transaction, returns `None` if it did not fail.
+    pub fn cadence_error(&self) -> Option<CadenceError> {
+        CadenceError::parse(&self.error_message)
+    }
+}
+
+impl TransactionStatusError {
+    /// Parses the error of the transaction, returns `None` if it did not fail to execute.
+    pub fn cadence_error(&self) -> Option<CadenceError> {
+        match self {
+            Self::Failed { error_message, .. } => CadenceError::parse(error_message),
+            _ => None,
+        }
+    }
+}
+
+impl TonicError {
+    /// Parses the error of a failed script, returns `None` if the request failed for another
+    /// reason.
+    pub fn cadence_error(&self) -> Option<CadenceError> {
+        match self {
+            Self::Status(status) => {
+                CadenceError::parse(status.message()).filter(|error| error.code.is_some())
+            }
+            Self::Custom(e) => CadenceError::from_error(&**e),
+        }
+    }
+}
@@ -214,6 +214,15 @@ impl<Inner> FlowClient<Inner> {
         }
 
         /// Sends a transaction over the network.
+        ///
+        /// The transaction is sent as is, without being checked against [`TransactionLimits`]:
+        /// the client does not know which network it talks to, and the envelope signatures may
+        /// only be produced while the transaction is encoded. Transactions sent by an [`Account`]
+        /// are checked, others can be checked with [`TransactionLimits::check_transaction`] first.
+        ///
+        /// [`TransactionLimits`]: crate::transaction::TransactionLimits
+        /// [`TransactionLimits::check_transaction`]: crate::transaction::TransactionLimits::check_transaction
+        /// [`Account`]: crate::account::Account
         pub async fn send_transaction<(
             Script,
             Arguments,
@@ -0,0 +1,464 @@
+//! Networks, accounts and contracts configured in a `flow.json` file.
+//!
+//! This reads the configuration file used by the Flow CLI, so that you can connect to the
+//! networks and log in to the accounts configured for your project by name:
+//!
+//! ```rust,no_run
+//! # async fn run() -> Result<(), flow_sdk::error::ConfigError> {
+//! use flow_sdk::config::Config;
+//!
+//! let config = Config::load()?;
+//! let account = config.account("emulator-account").await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Secret keys can be provided as hex, in a file holding the hex, or in an environment variable
+//! by writing `$VARIABLE` in place of the hex.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cadence_json::ValueOwned;
+use serde::Deserialize;
+use zeroize::Zeroizing;
+
+use crate::access::{AccountResponse, GetAccountAtLatestBlockRequest};
+use crate::account::AnyAccount;
+use crate::algorithms::{AnyHashAlgorithm, AnySecretKey, AnySignatureAlgorithm};
+use crate::client::{GrpcClient, TonicHyperFlowClient};
+pub use crate::error::ConfigError as Error;
+
+/// The name of the configuration file used by the Flow CLI.
+pub const CONFIG_FILE: &str = "flow.json";
+
+/// The network used by [`Config::account`] unless specified otherwise.
+pub const DEFAULT_NETWORK: &str = "emulator";
+
+/// A `flow.json` configuration.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    /// The networks by name.
+    #[serde(default)]
+    pub networks: HashMap<String, NetworkConfig>,
+
+    /// The accounts by name.
+    #[serde(default)]
+    pub accounts: HashMap<String, AccountConfig>,
+
+    /// The contracts by name.
+    #[serde(default)]
+    pub contracts: HashMap<String, ContractConfig>,
+
+    /// The contracts deployed to each account, by network name and then by account name.
+    #[serde(default)]
+    pub deployments: HashMap<String, HashMap<String, Vec<DeploymentConfig>>>,
+
+    #[serde(skip)]
+    base_dir: PathBuf,
+
+    #[serde(skip, default = "default_network")]
+    default_network: String,
+}
+
+/// A network to connect to.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "RawNetwork")]
+pub struct NetworkConfig {
+    /// The host and port of the access node.
+    pub host: String,
+    /// The public key of the access node, if secured.
+    pub key: Option<String>,
+}
+
+/// An account with one secret key.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawAccount")]
+pub struct AccountConfig {
+    /// The address of the account.
+    pub address: Box<[u8]>,
+    /// The secret key of the account.
+    pub key: KeyConfig,
+}
+
+/// A secret key of an account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyConfig {
+    /// Where to find the secret key.
+    pub source: KeySource,
+    /// The index of the key in the account.
+    pub index: u32,
+    /// The signature algorithm of the key.
+    pub sign_algo: AnySignatureAlgorithm,
+    /// The hashing algorithm of the key.
+    pub hash_algo: AnyHashAlgorithm,
+}
+
+/// Where to find a secret key.
+#[derive(Clone, PartialEq, Eq)]
+pub enum KeySource {
+    /// The secret key in hex.
+    Hex(Zeroizing<String>),
+    /// A file holding the secret key in hex, relative to the configuration file.
+    File(PathBuf),
+    /// An environment variable holding the secret key in hex.
+    Env(String),
+}
+
+/// A contract and its addresses on the networks where it is already deployed.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawContract")]
+pub struct ContractConfig {
+    /// The path of the source code, relative to the configuration file.
+    pub source: PathBuf,
+    /// The addresses of the contract by network name.
+    pub aliases: HashMap<String, Box<[u8]>>,
+}
+
+/// A contract to be deployed to an account.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "RawDeployment")]
+pub struct DeploymentConfig {
+    /// The name of the contract.
+    pub name: String,
+    /// The arguments passed to the initializer of the contract.
+    pub args: Vec<ValueOwned>,
+}
+
+impl Config {
+    /// Loads `flow.json` from the current directory.
+    pub fn load() -> Result<Self, Error> {
+        Self::load_from(CONFIG_FILE)
+    }
+
+    /// Loads a configuration file.
+    ///
+    /// Paths of key files and contracts are relative to the directory of the configuration file.
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let mut config = Self::from_json(&fs::read_to_string(path)?)?;
+        config.base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(config)
+    }
+
+    /// Parses a configuration. Paths are relative to the current directory.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Sets the network used by [`Config::account`]. Defaults to [`DEFAULT_NETWORK`].
+    pub fn with_default_network(mut self, network: impl Into<String>) -> Self {
+        self.default_network = network.into();
+        self
+    }
+
+    /// Returns the network used by [`Config::account`].
+    #[inline]
+    pub fn default_network(&self) -> &str {
+        &self.default_network
+    }
+
+    /// Returns the network configured by name.
+    pub fn network(&self, name: &str) -> Result<&NetworkConfig, Error> {
+        self.networks
+            .get(name)
+            .ok_or_else(|| Error::NetworkNotFound(name.into()))
+    }
+
+    /// Returns the account configured by name.
+    pub fn account_config(&self, name: &str) -> Result<&AccountConfig, Error> {
+        self.accounts
+            .get(name)
+            .ok_or_else(|| Error::AccountNotFound(name.into()))
+    }
+
+    /// Returns the contract configured by name.
+    pub fn contract(&self, name: &str) -> Result<&ContractConfig, Error> {
+        self.contracts
+            .get(name)
+            .ok_or_else(|| Error::ContractNotFound(name.into()))
+    }
+
+    /// Reads the source code of a contract.
+    pub fn contract_source(&self, name: &str) -> Result<String, Error> {
+        let contract = self.contract(name)?;
+        Ok(fs::read_to_string(self.base_dir.join(&contract.source))?)
+    }
+
+    /// Returns the address of a contract on a network.
+    ///
+    /// This is the alias of the contract for the network, or the address of the account the
+    /// contract is deployed to on the network.
+    pub fn contract_address(&self, contract: &str, network: &str) -> Option<&[u8]> {
+        if let Some(address) = self
+            .contracts
+            .get(contract)
+            .and_then(|contract| contract.aliases.get(network))
+        {
+            return Some(address);
+        }
+
+        self.deployments
+            .get(network)?
+            .iter()
+            .find(|(_, contracts)| contracts.iter().any(|c| c.name == contract))
+            .and_then(|(account, _)| self.accounts.get(account))
+            .map(|account| &*account.address)
+    }
+
+    /// Reads the secret key of an account from its source.
+    pub fn secret_key(&self, account: &str) -> Result<AnySecretKey, Error> {
+        let AccountConfig { key, .. } = self.account_config(account)?;
+
+        let hex = match &key.source {
+            KeySource::Hex(hex) => hex.clone(),
+            KeySource::File(path) => Zeroizing::new(fs::read_to_string(self.base_dir.join(path))?),
+            KeySource::Env(var) => {
+                Zeroizing::new(std::env::var(var).map_err(|_| Error::EnvVarNotFound(var.clone()))?)
+            }
+        };
+
+        let bytes = hex::decode(hex.trim().trim_start_matches("0x"))
+            .map(Zeroizing::new)
+            .map_err(|_| Error::InvalidKeyHex(account.into()))?;
+
+        Ok(AnySecretKey::new(
+            key.sign_algo.code(),
+            key.hash_algo.code(),
+            &bytes,
+        )?)
+    }
+
+    /// Connects to a network by name.
+    ///
+    /// Secured networks, configured with the public key of their access node, are not supported
+    /// yet. They are rejected rather than connected to without encryption.
+    pub async fn client(&self, network: &str) -> Result<TonicHyperFlowClient, Error> {
+        let config = self.network(network)?;
+        if config.key.is_some() {
+            return Err(Error::SecureNetworkUnsupported(network.into()));
+        }
+
+        let endpoint = tonic::transport::Endpoint::from_shared(format!("http://{}", config.host))?;
+        Ok(TonicHyperFlowClient::connect(endpoint).await?)
+    }
+
+    /// Logs in to an account by name, on the default network.
+    pub async fn account(&self, name: &str) -> Result<AnyAccount<TonicHyperFlowClient>, Error> {
+        self.account_on(name, &self.default_network).await
+    }
+
+    /// Logs in to an account by name, on a network by name.
+    pub async fn account_on(
+        &self,
+        name: &str,
+        network: &str,
+    ) -> Result<AnyAccount<TonicHyperFlowClient>, Error> {
+        let client = self.client(network).await?;
+        self.account_with(name, client).await
+    }
+
+    /// Logs in to an account by name, using the client provided.
+    pub async fn account_with<Client>(
+        &self,
+        name: &str,
+        client: Client,
+    ) -> Result<AnyAccount<Client>, Error>
+    where
+        Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    {
+        let secret_key = self.secret_key(name)?;
+        let config = self.account_config(name)?;
+        let account = AnyAccount::new(client, &*config.address, secret_key).await?;
+
+        if account.primary_key_id() != config.key.index {
+            return Err(Error::KeyIndexMismatch {
+                expected: config.key.index,
+                found: account.primary_key_id(),
+            });
+        }
+
+        Ok(account)
+    }
+}
+
+impl std::fmt::Debug for KeySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Hex(_) => f.write_str("Hex(..)"),
+            Self::File(path) => f.debug_tuple("File").field(path).finish(),
+            Self::Env(var) => f.debug_tuple("Env").field(var).finish(),
+        }
+    }
+}
+
+fn default_network() -> String {
+    DEFAULT_NETWORK.into()
+}
+
+fn parse_address(address: &str) -> Result<Box<[u8]>, String> {
+    hex::decode(address.trim_start_matches("0x"))
+        .map(Into::into)
+        .map_err(|_| format!("invalid address: {}", address))
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawNetwork {
+    Host(String),
+    Advanced { host: String, key: Option<String> },
+}
+
+impl From<RawNetwork> for NetworkConfig {
+    fn from(raw: RawNetwork) -> Self {
+        match raw {
+            RawNetwork::Host(host) => Self { host, key: None },
+            RawNetwork::Advanced { host, key } => Self { host, key },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawAccount {
+    address: String,
+    key: RawKey,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawKey {
+    Simple(String),
+    Advanced {
+        #[serde(rename = "type")]
+        ty: String,
+        #[serde(default)]
+        index: u32,
+        #[serde(rename = "signatureAlgorithm")]
+        sign_algo: Option<String>,
+        #[serde(rename = "hashAlgorithm")]
+        hash_algo: Option<String>,
+        #[serde(rename = "privateKey")]
+        private_key: Option<String>,
+        location: Option<PathBuf>,
+    },
+}
+
+fn hex_source(hex: String) -> KeySource {
+    match hex.strip_prefix('$') {
+        Some(var) => KeySource::Env(var.trim_matches(|c| c == '{' || c == '}').into()),
+        None => KeySource::Hex(Zeroizing::new(hex)),
+    }
+}
+
+impl TryFrom<RawAccount> for AccountConfig {
+    type Error = String;
+
+    fn try_from(raw: RawAccount) -> Result<Self, String> {
+        // The Flow CLI defaults to these algorithms.
+        let mut sign_algo = AnySignatureAlgorithm::P256;
+        let mut hash_algo = AnyHashAlgorithm::Sha3;
+
+        let key = match raw.key {
+            RawKey::Simple(hex) => KeyConfig {
+                source: hex_source(hex),
+                index: 0,
+                sign_algo,
+                hash_algo,
+            },
+            RawKey::Advanced {
+                ty,
+                index,
+                sign_algo: sign_algo_name,
+                hash_algo: hash_algo_name,
+                private_key,
+                location,
+            } => {
+                if let Some(name) = sign_algo_name {
+                    sign_algo = AnySignatureAlgorithm::from_name(&name)
+                        .ok_or_else(|| format!("unsupported signature algorithm: {}", name))?;
+                }
+
+                if let Some(name) = hash_algo_name {
+                    hash_algo = AnyHashAlgorithm::from_name(&name)
+                        .ok_or_else(|| format!("unsupported hashing algorithm: {}", name))?;
+                }
+
+                let source = match (&*ty, private_key, location) {
+                    ("hex", Some(hex), _) => hex_source(hex),
+                    ("file", _, Some(location)) => KeySource::File(location),
+                    ("hex", None, _) => return Err("missing field `privateKey`".into()),
+                    ("file", _, None) => return Err("missing field `location`".into()),
+                    (ty, _, _) => return Err(format!("unsupported key type: {}", ty)),
+                };
+
+                KeyConfig {
+                    source,
+                    index,
+                    sign_algo,
+                    hash_algo,
+                }
+            }
+        };
+
+        Ok(Self {
+            address: parse_address(&raw.address)?,
+            key,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawContract {
+    Source(PathBuf),
+    Advanced {
+        source: PathBuf,
+        #[serde(default)]
+        aliases: HashMap<String, String>,
+    },
+}
+
+impl TryFrom<RawContract> for ContractConfig {
+    type Error = String;
+
+    fn try_from(raw: RawContract) -> Result<Self, String> {
+        match raw {
+            RawContract::Source(source) => Ok(Self {
+                source,
+                aliases: HashMap::new(),
+            }),
+            RawContract::Advanced { source, aliases } => Ok(Self {
+                source,
+                aliases: aliases
+                    .into_iter()
+                    .map(|(network, address)| Ok((network, parse_address(&address)?)))
+                    .collect::<Result<_, String>>()?,
+            }),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawDeployment {
+    Name(String),
+    WithArgs {
+        name: String,
+        #[serde(default)]
+        args: Vec<ValueOwned>,
+    },
+}
+
+impl From<RawDeployment> for DeploymentConfig {
+    fn from(raw: RawDeployment) -> Self {
+        match raw {
+            RawDeployment::Name(name) => Self {
+                name,
+                args: Vec::new(),
+            },
+            RawDeployment::WithArgs { name, args } => Self { name, args },
+        }
+    }
+}
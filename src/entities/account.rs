@@ -1,5 +1,13 @@
 use otopr::{DecodableMessage, Map, Repeated};
 
+#[cfg(feature = "any-algorithms")]
+use crate::algorithms::{
+    secp256k1, verify_account_key, AnyHasher, FlowHasher, P256Verifier, Secp256k1,
+    SignatureAlgorithm, P256,
+};
+#[cfg(feature = "any-algorithms")]
+use crate::error::VerifyError;
+
 /// An account is a user's identity on Flow.
 /// It contains a unique address, a balance,
 /// a list of public keys and the code that has been deployed to the account.
@@ -49,3 +57,27 @@ pub struct AccountKey {
     /// Whether this key is revoked.
     pub revoked: bool,
 }
+
+#[cfg(feature = "any-algorithms")]
+impl AccountKey {
+    /// Verifies a signature of data made by this key, hashing the data with the hashing algorithm
+    /// of this key.
+    ///
+    /// Fails if the key is revoked, or if its algorithms are not supported.
+    pub fn verify(&self, data: impl AsRef<[u8]>, signature: &[u8]) -> Result<(), VerifyError> {
+        let mut hasher = AnyHasher::new();
+        hasher.update(data.as_ref());
+
+        match self.sign_algo {
+            Secp256k1::CODE => verify_account_key(
+                &secp256k1::Secp256k1::verification_only(),
+                self,
+                hasher,
+                signature,
+            ),
+            P256::CODE => verify_account_key(&P256Verifier, self, hasher, signature),
+            _ if self.revoked => Err(VerifyError::KeyRevoked),
+            _ => Err(VerifyError::AlgoMismatch),
+        }
+    }
+}
@@ -33,6 +33,670 @@ pub enum AccountError {
     Custom(#[from] Box<dyn Error + Send + Sync>),
 }
 
+/// The errors that could happen when sending a transaction with
+/// [`Account::send_transaction_header`] or [`Account::send_transaction_header_with`].
+///
+/// [`Account::send_transaction_header`]: crate::account::Account::send_transaction_header
+/// [`Account::send_transaction_header_with`]: crate::account::Account::send_transaction_header_with
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SendTransactionError {
+    /// The proposal key of the account could not be found on chain, so its sequence number is
+    /// unknown.
+    #[error("Could not find the proposal key with index {0}.")]
+    ProposalKeyNotFound(u32),
+
+    /// The transaction exceeds the limits of the network, and was not sent.
+    #[error(transparent)]
+    Limit(#[from] LimitError),
+
+    /// A signer could not sign the transaction, which was not sent.
+    #[error("Could not sign the transaction: {0}")]
+    Signing(#[source] Box<dyn Error + Send + Sync>),
+
+    /// An error from the client has occured.
+    #[error(transparent)]
+    Custom(#[from] Box<dyn Error + Send + Sync>),
+}
+
+/// The limits of the network that a transaction exceeds, found by [`TransactionLimits`].
+///
+/// [`TransactionLimits`]: crate::transaction::TransactionLimits
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum LimitError {
+    /// The gas limit is higher than the maximum.
+    #[error("The gas limit {gas_limit} is higher than the maximum of {max}.")]
+    GasLimitTooHigh {
+        /// The gas limit of the transaction.
+        gas_limit: u64,
+        /// The maximum gas limit.
+        max: u64,
+    },
+
+    /// The script is empty.
+    #[error("The script is empty.")]
+    EmptyScript,
+
+    /// The script is larger than the maximum.
+    #[error("The script is {size} bytes, more than the maximum of {max}.")]
+    ScriptTooLarge {
+        /// The size of the script, in bytes.
+        size: usize,
+        /// The maximum size, in bytes.
+        max: usize,
+    },
+
+    /// There are more arguments than the maximum.
+    #[error("There are {count} arguments, more than the maximum of {max}.")]
+    TooManyArguments {
+        /// The number of arguments.
+        count: usize,
+        /// The maximum number of arguments.
+        max: usize,
+    },
+
+    /// An argument is larger than the maximum.
+    #[error("The argument at index {index} is {size} bytes, more than the maximum of {max}.")]
+    ArgumentTooLarge {
+        /// The index of the argument.
+        index: usize,
+        /// The size of the argument, in bytes.
+        size: usize,
+        /// The maximum size, in bytes.
+        max: usize,
+    },
+
+    /// The transaction is larger than the maximum.
+    #[error("The transaction is {size} bytes, more than the maximum of {max}.")]
+    TransactionTooLarge {
+        /// The size of the transaction, in bytes.
+        size: usize,
+        /// The maximum size, in bytes.
+        max: usize,
+    },
+}
+
+/// The errors that could happen when sending a transaction with
+/// [`Account::send_transaction_header_resubmit`].
+///
+/// [`Account::send_transaction_header_resubmit`]: crate::account::Account::send_transaction_header_resubmit
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ResubmitError {
+    /// The transaction could not be sent.
+    #[error(transparent)]
+    Send(#[from] SendTransactionError),
+
+    /// The transaction was executed, but failed.
+    #[error("The transaction failed with status code {status_code}: {error_message}")]
+    Failed {
+        /// The ID of the transaction.
+        id: Vec<u8>,
+        /// The status code of the transaction.
+        status_code: u32,
+        /// The error message of the transaction.
+        error_message: String,
+    },
+
+    /// The transaction did not reach the status waited for within the timeout. It is not
+    /// resubmitted, since it could still be executed.
+    #[error("Timed out while waiting for the transaction.")]
+    TimedOut {
+        /// The ID of the transaction.
+        id: Vec<u8>,
+    },
+
+    /// Every attempt expired or was dropped.
+    #[error("The transaction expired or was dropped {attempts} times.")]
+    AttemptsExhausted {
+        /// The number of attempts.
+        attempts: u32,
+        /// The ID of the last transaction submitted.
+        last_id: Vec<u8>,
+    },
+
+    /// An error from the client has occured.
+    #[error(transparent)]
+    Custom(#[from] Box<dyn Error + Send + Sync>),
+}
+
+/// The errors that could happen when following the status of a transaction with a
+/// [`StatusStream`].
+///
+/// [`StatusStream`]: crate::transaction::StatusStream
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum TransactionStatusError {
+    /// The transaction failed to execute.
+    #[error("The transaction failed with status code {status_code}: {error_message}")]
+    Failed {
+        /// The status code of the transaction.
+        status_code: u32,
+        /// The error message of the transaction.
+        error_message: String,
+    },
+
+    /// The transaction expired before reaching the status waited for.
+    #[error("The transaction expired.")]
+    Expired,
+
+    /// The timeout has been reached.
+    #[error("Timed out while waiting for the transaction.")]
+    Timeout,
+
+    /// An error from the client has occured.
+    #[error(transparent)]
+    Custom(#[from] Box<dyn Error + Send + Sync>),
+}
+
+/// The errors that could happen when resyncing the keys of a [`ProposerPool`].
+///
+/// [`ProposerPool`]: crate::proposer::ProposerPool
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ProposerPoolError {
+    /// A key of the pool could not be found on the account.
+    #[error("Could not find the key with index {0}.")]
+    KeyNotFound(u32),
+
+    /// A key of the pool is revoked.
+    #[error("The key with index {0} is revoked.")]
+    KeyRevoked(u32),
+
+    /// An error from the client has occured.
+    #[error(transparent)]
+    Custom(#[from] Box<dyn Error + Send + Sync>),
+}
+
+/// The errors that could happen to a transaction sent by a [`Pipeline`].
+///
+/// [`Pipeline`]: crate::pipeline::Pipeline
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum PipelineError {
+    /// The transaction could not be sent.
+    #[error(transparent)]
+    Send(#[from] SendTransactionError),
+
+    /// The transaction failed, expired or was not sealed within the timeout.
+    #[error(transparent)]
+    Status(#[from] TransactionStatusError),
+
+    /// A proposal key could not be acquired, so the transaction was not sent.
+    #[error(transparent)]
+    Proposer(#[from] ProposerPoolError),
+
+    /// Every key of the proposer pool is used outside of the pipeline, so the transaction was not
+    /// sent.
+    #[error("Every key of the proposer pool is in use.")]
+    NoFreeKey,
+}
+
+/// The errors that could happen when importing, converting or merging a [`PortableParty`].
+///
+/// [`PortableParty`]: crate::multi::interchange::PortableParty
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum InterchangeError {
+    /// The party is not valid JSON.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// The party is not a valid RLP encoded transaction.
+    #[error("Invalid RLP encoding: {0}")]
+    Rlp(#[from] rlp::DecoderError),
+
+    /// The party has an unsupported version.
+    #[error("Unsupported party version: {0}")]
+    UnsupportedVersion(u32),
+
+    /// A field of the party is invalid.
+    #[error("Invalid party field: {0}")]
+    InvalidField(&'static str),
+
+    /// A signature was made by an account that is not expected to sign the transaction.
+    #[error("A signature was made by an account that does not take part in the transaction.")]
+    UnknownSigner,
+
+    /// The parties being merged are for different transactions.
+    #[error("The parties are for different transactions.")]
+    PayloadMismatch,
+
+    /// A payload signature was added after the envelope was signed.
+    #[error("The envelope has already been signed.")]
+    EnvelopeSigned,
+
+    /// The signer could not sign the party.
+    #[error("Could not sign the party: {0}")]
+    Signing(#[source] Box<dyn Error + Send + Sync>),
+}
+
+/// The errors that could happen when converting or signing the objects of FCL.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum FclError {
+    /// The request is not valid JSON, or not a valid FCL object.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// A field of the request is invalid.
+    #[error("Invalid field: {0}")]
+    InvalidField(&'static str),
+
+    /// The voucher could not be converted into a party, or signed.
+    #[error(transparent)]
+    Interchange(#[from] InterchangeError),
+
+    /// The request asks another account to sign.
+    #[error("The request asks another account to sign.")]
+    WrongSigner,
+
+    /// The message to sign is not the payload or the envelope of the voucher.
+    #[error("The message does not match the voucher.")]
+    MessageMismatch,
+
+    /// The account did not sign with the key asked.
+    #[error("Could not sign with the key with index {0}.")]
+    KeyNotFound(u32),
+}
+
+/// The errors that could happen when collecting signatures in a [`SigningSession`].
+///
+/// [`SigningSession`]: crate::multi::session::SigningSession
+#[cfg(feature = "any-algorithms")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SigningSessionError {
+    /// The account of a signer was not provided.
+    #[error("The account of a signer is missing.")]
+    MissingAccount,
+
+    /// The signature was made by an account that does not sign in this role.
+    #[error("The signature was made by an account that does not sign in this role.")]
+    WrongRole,
+
+    /// A payload signature was added after the envelope was signed.
+    #[error("The envelope has already been signed.")]
+    EnvelopeSigned,
+
+    /// An envelope signature was added before the payload was fully signed.
+    #[error("The payload has not been signed by every proposer and authorizer.")]
+    PayloadIncomplete,
+
+    /// The transaction was taken out before every signer has signed.
+    #[error("Not every signer has signed the transaction.")]
+    Incomplete,
+
+    /// The account already signed with the key.
+    #[error("More than one signature was made by the key with index {0}.")]
+    DuplicateKey(u32),
+
+    /// The account does not have a key with the index of the signature.
+    #[error("Could not find a key with index {0}.")]
+    KeyNotFound(u32),
+
+    /// The signature could not be verified.
+    #[error("Could not verify the signature of the key with index {key_id}: {error}")]
+    InvalidSignature {
+        /// The index of the key.
+        key_id: u32,
+        /// Why the signature could not be verified.
+        #[source]
+        error: VerifyError,
+    },
+
+    /// The signer could not sign the party.
+    #[error("Could not sign the party: {0}")]
+    Signing(#[source] Box<dyn Error + Send + Sync>),
+
+    /// An error from the client has occured.
+    #[error(transparent)]
+    Custom(#[from] Box<dyn Error + Send + Sync>),
+}
+
+/// The errors that could happen when a [`PayerService`] is asked to pay for a transaction.
+///
+/// [`PayerService`]: crate::payer::PayerService
+#[cfg(feature = "payer-service")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum PayerError {
+    /// The payer of the transaction is another account.
+    #[error("The transaction is paid for by another account.")]
+    WrongPayer,
+
+    /// The proposer or an authorizer has not signed the payload.
+    #[error("The account 0x{} has not signed the payload.", hex::encode(.0))]
+    MissingSignature(Box<[u8]>),
+
+    /// The script of the transaction is not allowed, with the hash of the script.
+    #[error("The script with hash {} is not allowed.", hex::encode(.0))]
+    ScriptNotAllowed([u8; 32]),
+
+    /// The gas limit of the transaction is higher than the maximum of the policy.
+    #[error("The gas limit {gas_limit} is higher than the maximum of {max}.")]
+    GasLimitTooHigh {
+        /// The gas limit of the transaction.
+        gas_limit: u64,
+        /// The maximum gas limit of the policy.
+        max: u64,
+    },
+
+    /// The proposer or an authorizer has reached the rate limit.
+    #[error("The account 0x{} has reached the rate limit.", hex::encode(.0))]
+    RateLimited(Box<[u8]>),
+
+    /// A payload signature was rejected, or the accounts of the signers could not be queried.
+    #[error(transparent)]
+    Session(#[from] SigningSessionError),
+
+    /// The party could not be imported or signed.
+    #[error(transparent)]
+    Interchange(#[from] InterchangeError),
+}
+
+/// The errors that could happen when creating an account with [`Account::create_account`].
+///
+/// [`Account::create_account`]: crate::account::Account::create_account
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum CreateAccountError {
+    /// The transaction could not be sent.
+    #[error(transparent)]
+    Send(#[from] SendTransactionError),
+
+    /// The transaction was executed, but failed.
+    #[error("The transaction failed with status code {status_code}: {error_message}")]
+    Reverted {
+        /// The status code of the transaction.
+        status_code: u32,
+        /// The error message of the transaction.
+        error_message: String,
+    },
+
+    /// The transaction expired before it was sealed.
+    #[error("The transaction has expired.")]
+    Expired,
+
+    /// The transaction was not sealed within the timeout.
+    #[error("Timed out while waiting for the transaction to be sealed.")]
+    TimedOut,
+
+    /// The transaction was sealed, but no `flow.AccountCreated` event was emitted.
+    #[error("Could not find the address of the created account.")]
+    NoAccountCreated,
+
+    /// An error from the client has occured.
+    #[error(transparent)]
+    Custom(#[from] Box<dyn Error + Send + Sync>),
+}
+
+/// The errors that could happen when creating a secret key from raw bytes.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SecretKeyError {
+    /// The signature algorithm is not supported.
+    #[error("Unsupported signature algorithm: {0}")]
+    UnsupportedSignatureAlgorithm(u32),
+
+    /// The hashing algorithm is not supported.
+    #[error("Unsupported hashing algorithm: {0}")]
+    UnsupportedHashAlgorithm(u32),
+
+    /// The bytes do not form a valid secret key.
+    #[error("Invalid secret key.")]
+    InvalidSecretKey,
+}
+
+/// The errors that could happen when verifying a signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum VerifyError {
+    /// The signature does not match the data and the public key.
+    #[error("The signature is invalid.")]
+    InvalidSignature,
+
+    /// The bytes do not form a signature.
+    #[error("The signature is malformed.")]
+    MalformedSignature,
+
+    /// The bytes do not form a public key.
+    #[error("The public key is malformed.")]
+    MalformedPublicKey,
+
+    /// The hashing and signing algorithms used did not match the account key.
+    #[error("The hashing and signing algorithms do not match.")]
+    AlgoMismatch,
+
+    /// The account key is revoked.
+    #[error("The key is revoked.")]
+    KeyRevoked,
+}
+
+/// The errors that could happen when verifying signatures made by the keys of an account.
+#[cfg(feature = "any-algorithms")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum VerifySignaturesError {
+    /// A signature was made by a different account.
+    #[error("A signature was made by a different account.")]
+    AddressMismatch,
+
+    /// The account does not have a key with the index of a signature.
+    #[error("Could not find a key with index {0}.")]
+    KeyNotFound(u32),
+
+    /// More than one signature was made by the same key.
+    #[error("More than one signature was made by the key with index {0}.")]
+    DuplicateKey(u32),
+
+    /// A signature could not be verified.
+    #[error("Could not verify the signature of the key with index {key_id}: {error}")]
+    InvalidSignature {
+        /// The index of the key.
+        key_id: u32,
+        /// Why the signature could not be verified.
+        #[source]
+        error: VerifyError,
+    },
+
+    /// The keys that signed do not have enough weight in total.
+    #[error("The keys have a total weight of {0}, which is less than 1000.")]
+    NotEnoughWeight(u32),
+
+    /// The nonce of an account proof is shorter than 32 bytes.
+    #[error("The nonce must be at least 32 bytes long.")]
+    NonceTooShort,
+
+    /// The address of an account proof is longer than 8 bytes.
+    #[error("The address must be at most 8 bytes long.")]
+    InvalidAddress,
+
+    /// An error from the client has occured.
+    #[error(transparent)]
+    Custom(#[from] Box<dyn Error + Send + Sync>),
+}
+
+/// The errors that could happen when signing with a [`RemoteSigner`].
+///
+/// [`RemoteSigner`]: crate::algorithms::RemoteSigner
+#[cfg(feature = "remote-sign")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum RemoteSignerError {
+    /// The endpoint of the signing service is not a valid URI.
+    #[error(transparent)]
+    InvalidUri(#[from] http::uri::InvalidUri),
+
+    /// An error occured while making the request.
+    #[error(transparent)]
+    Http(#[from] hyper::Error),
+
+    /// The response was not valid JSON.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// The signing service responded with an error.
+    #[error("The signing service responded with status {status}: {message}")]
+    Status {
+        /// The HTTP status code of the response.
+        status: u16,
+        /// The body of the response.
+        message: String,
+    },
+
+    /// The response did not contain the expected field.
+    #[error("Invalid response from the signing service: missing or invalid `{0}`")]
+    InvalidResponse(&'static str),
+
+    /// The digest does not contain a hash computed with the hashing algorithm of the key.
+    #[error("The data was not hashed with the hashing algorithm of the key: {0}")]
+    MissingHash(u32),
+}
+
+/// The errors that could happen when saving, loading or unlocking a [`Keystore`].
+///
+/// [`Keystore`]: crate::keystore::Keystore
+#[cfg(feature = "keystore")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum KeystoreError {
+    /// An I/O error occured while reading or writing the keystore file.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// The keystore file is not valid JSON.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// The keystore file has an unsupported version.
+    #[error("Unsupported keystore version: {0}")]
+    UnsupportedVersion(u32),
+
+    /// The keystore file uses an unsupported key derivation function or cipher.
+    #[error("Unsupported {0}: {1}")]
+    Unsupported(&'static str, String),
+
+    /// A field of the keystore file is invalid.
+    #[error("Invalid keystore field: {0}")]
+    InvalidField(&'static str),
+
+    /// The parameters of the key derivation function are invalid, or above the ceilings of
+    /// [`KdfParams`].
+    ///
+    /// [`KdfParams`]: crate::keystore::KdfParams
+    #[error("Invalid key derivation parameters.")]
+    InvalidParams,
+
+    /// The password is incorrect, or the keystore file has been tampered with.
+    #[error("Incorrect password, or the keystore has been tampered with.")]
+    Decryption,
+
+    /// The secret key is invalid.
+    #[error(transparent)]
+    SecretKey(#[from] SecretKeyError),
+
+    /// Could not log in to the account.
+    #[error(transparent)]
+    Account(#[from] AccountError),
+
+    /// The secret key matched an account key with a different index than the one recorded.
+    #[error("Expected the key to have index {expected}, found {found}")]
+    KeyIndexMismatch {
+        /// The key index recorded in the keystore.
+        expected: u32,
+        /// The index of the matching account key.
+        found: u32,
+    },
+}
+
+/// The errors that could happen when reading a `flow.json` configuration, or when connecting to
+/// networks and accounts configured in it.
+#[cfg(feature = "config")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// An I/O error occured while reading the configuration or a file referenced by it.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// The configuration is not valid.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// The network is not configured.
+    #[error("Network not found: {0}")]
+    NetworkNotFound(String),
+
+    /// The account is not configured.
+    #[error("Account not found: {0}")]
+    AccountNotFound(String),
+
+    /// The contract is not configured.
+    #[error("Contract not found: {0}")]
+    ContractNotFound(String),
+
+    /// The environment variable holding a secret key is not set.
+    #[error("Environment variable not found: {0}")]
+    EnvVarNotFound(String),
+
+    /// The secret key is not valid hex.
+    #[error("The secret key of account {0} is not valid hex.")]
+    InvalidKeyHex(String),
+
+    /// The secret key is invalid.
+    #[error(transparent)]
+    SecretKey(#[from] SecretKeyError),
+
+    /// The host of the network is not valid.
+    #[error(transparent)]
+    InvalidHost(#[from] http::uri::InvalidUri),
+
+    /// The network is secured with the public key of its access node, which is not supported.
+    #[error("Secure connections to network {0} are not supported.")]
+    SecureNetworkUnsupported(String),
+
+    /// Could not connect to the network.
+    #[error(transparent)]
+    Transport(#[from] tonic::transport::Error),
+
+    /// Could not log in to the account.
+    #[error(transparent)]
+    Account(#[from] AccountError),
+
+    /// The secret key matched an account key with a different index than the one configured.
+    #[error("Expected the key to have index {expected}, found {found}")]
+    KeyIndexMismatch {
+        /// The key index in the configuration.
+        expected: u32,
+        /// The index of the matching account key.
+        found: u32,
+    },
+}
+
+/// The errors that could happen when generating mnemonics or deriving keys.
+#[cfg(feature = "hd-wallet")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum HdError {
+    /// The mnemonic is invalid.
+    #[error(transparent)]
+    Mnemonic(#[from] bip39::Error),
+
+    /// The derivation path is invalid.
+    #[error("Invalid derivation path: {0}")]
+    InvalidPath(String),
+
+    /// Could not obtain randomness from the operating system.
+    #[error(transparent)]
+    Random(#[from] getrandom::Error),
+
+    /// The seed does not produce a valid master key for the curve.
+    #[error("The seed does not produce a valid master key.")]
+    InvalidSeed,
+}
+
 /// The errors that could happen when sending a request via tonic.
 #[derive(Debug, thiserror::Error)]
 pub enum TonicError {
@@ -52,6 +716,42 @@ impl From<Box<dyn Error + Send + Sync>> for BoxError {
     }
 }
 
+impl From<SendTransactionError> for BoxError {
+    #[inline]
+    fn from(e: SendTransactionError) -> Self {
+        Self(Box::new(e))
+    }
+}
+
+impl From<BoxError> for CreateAccountError {
+    #[inline]
+    fn from(e: BoxError) -> Self {
+        Self::Custom(e.0)
+    }
+}
+
+impl From<BoxError> for SendTransactionError {
+    #[inline]
+    fn from(e: BoxError) -> Self {
+        Self::Custom(e.0)
+    }
+}
+
+#[cfg(feature = "any-algorithms")]
+impl From<BoxError> for SigningSessionError {
+    #[inline]
+    fn from(e: BoxError) -> Self {
+        Self::Custom(e.0)
+    }
+}
+
+impl From<BoxError> for ProposerPoolError {
+    #[inline]
+    fn from(e: BoxError) -> Self {
+        Self::Custom(e.0)
+    }
+}
+
 impl fmt::Debug for BoxError {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
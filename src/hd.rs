@@ -0,0 +1,315 @@
+//! Secret keys derived from BIP-39 mnemonics.
+//!
+//! Keys are derived from the seed of a mnemonic following [SLIP-10], which is the same as
+//! BIP-32 for secp256k1 keys, and extends it to P-256 keys. Flow wallets use the BIP-44 path
+//! `m/44'/539'/0'/0/0`, see [`DerivationPath::flow`].
+//!
+//! Mnemonics can be validated by parsing them with [`Mnemonic::parse`].
+//!
+//! ```rust
+//! use flow_sdk::hd::{DerivationPath, Mnemonic, Seed};
+//!
+//! let mnemonic = Mnemonic::parse(
+//!     "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+//! )?;
+//! let seed = Seed::from_mnemonic(&mnemonic, "");
+//! let secret_key = seed.derive_secp256k1(&DerivationPath::flow(0, 0))?;
+//! # Ok::<(), flow_sdk::error::HdError>(())
+//! ```
+//!
+//! [SLIP-10]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+
+use std::fmt;
+use std::str::FromStr;
+
+use hmac::{Hmac, Mac};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::elliptic_curve::PrimeField;
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+pub use bip39::{Language, Mnemonic};
+
+use crate::algorithms::{secp256k1, AnyHashAlgorithm, AnySecretKey, AnySignatureAlgorithm};
+pub use crate::error::HdError as Error;
+
+/// The BIP-44 coin type of Flow.
+pub const FLOW_COIN_TYPE: u32 = 539;
+
+/// Added to child numbers to mark them as hardened.
+pub const HARDENED: u32 = 0x8000_0000;
+
+/// Generates a new mnemonic with the word count specified, which must be 12, 15, 18, 21 or 24.
+pub fn generate_mnemonic(word_count: usize) -> Result<Mnemonic, Error> {
+    if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+        return Err(bip39::Error::BadWordCount(word_count).into());
+    }
+
+    let mut entropy = Zeroizing::new([0; 32]);
+    let entropy = &mut entropy[..word_count / 3 * 4];
+    getrandom::getrandom(entropy)?;
+
+    Ok(Mnemonic::from_entropy(entropy)?)
+}
+
+/// A path of child numbers for deriving keys, such as `m/44'/539'/0'/0/0`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Box<[u32]>);
+
+impl DerivationPath {
+    /// Creates a path from child numbers. Hardened child numbers include [`HARDENED`].
+    pub fn new(children: impl Into<Box<[u32]>>) -> Self {
+        Self(children.into())
+    }
+
+    /// The path used by Flow wallets: `m/44'/539'/{account}'/0/{index}`.
+    pub fn flow(account: u32, index: u32) -> Self {
+        Self::new([
+            44 | HARDENED,
+            FLOW_COIN_TYPE | HARDENED,
+            account | HARDENED,
+            0,
+            index,
+        ])
+    }
+
+    /// Returns the child numbers of this path.
+    #[inline]
+    pub fn children(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidPath(s.into());
+
+        let mut parts = s.split('/');
+
+        if parts.next() != Some("m") {
+            return Err(invalid());
+        }
+
+        parts
+            .map(|part| {
+                let (number, hardened) = match part.strip_suffix(|c| matches!(c, '\'' | 'h' | 'H'))
+                {
+                    Some(number) => (number, HARDENED),
+                    None => (part, 0),
+                };
+
+                match number.parse::<u32>() {
+                    Ok(number) if number < HARDENED => Ok(number | hardened),
+                    _ => Err(invalid()),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|children| Self(children.into()))
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("m")?;
+        for &child in self.children() {
+            if child & HARDENED != 0 {
+                write!(f, "/{}'", child & !HARDENED)?;
+            } else {
+                write!(f, "/{}", child)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A seed for deriving keys. The memory of the seed is zeroized on drop.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Seed(Zeroizing<Vec<u8>>);
+
+impl Seed {
+    /// Creates a seed from raw bytes.
+    pub fn new(seed: impl Into<Vec<u8>>) -> Self {
+        Self(Zeroizing::new(seed.into()))
+    }
+
+    /// Computes the seed of a mnemonic with a passphrase, which can be empty.
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Self {
+        Self::new(mnemonic.to_seed(passphrase))
+    }
+
+    /// Returns the raw bytes of this seed.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Derives a secp256k1 secret key.
+    pub fn derive_secp256k1(&self, path: &DerivationPath) -> Result<secp256k1::SecretKey, Error> {
+        derive::<Secp256k1>(&self.0, path)
+    }
+
+    /// Derives a P-256 secret key.
+    pub fn derive_p256(&self, path: &DerivationPath) -> Result<p256::ecdsa::SigningKey, Error> {
+        derive::<P256>(&self.0, path).map(Into::into)
+    }
+
+    /// Derives a secret key of the signature algorithm specified, that uses the hashing algorithm
+    /// specified.
+    pub fn derive(
+        &self,
+        sign_algo: AnySignatureAlgorithm,
+        hash_algo: AnyHashAlgorithm,
+        path: &DerivationPath,
+    ) -> Result<AnySecretKey, Error> {
+        Ok(match sign_algo {
+            AnySignatureAlgorithm::Secp256k1 => {
+                AnySecretKey::secp256k1(self.derive_secp256k1(path)?, hash_algo)
+            }
+            AnySignatureAlgorithm::P256 => AnySecretKey::p256(self.derive_p256(path)?, hash_algo),
+        })
+    }
+}
+
+impl fmt::Debug for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Seed(..)")
+    }
+}
+
+/// A curve that keys can be derived for.
+trait Curve {
+    /// The HMAC key used to derive the master key.
+    const SEED_KEY: &'static [u8];
+
+    type SecretKey;
+
+    /// Parses a secret key, returns `None` if it is not in `1..n`.
+    fn parse(bytes: &[u8]) -> Option<Self::SecretKey>;
+
+    /// Adds a tweak to the parent key, returns `None` if the tweak is not less than `n` or if the
+    /// result is zero.
+    fn add(parent: &Self::SecretKey, tweak: &[u8]) -> Option<Self::SecretKey>;
+
+    fn to_bytes(key: &Self::SecretKey) -> Zeroizing<[u8; 32]>;
+
+    /// The compressed public key.
+    fn public_key(key: &Self::SecretKey) -> [u8; 33];
+}
+
+struct Secp256k1;
+
+impl Curve for Secp256k1 {
+    const SEED_KEY: &'static [u8] = b"Bitcoin seed";
+
+    type SecretKey = secp256k1::SecretKey;
+
+    fn parse(bytes: &[u8]) -> Option<Self::SecretKey> {
+        secp256k1::SecretKey::from_slice(bytes).ok()
+    }
+
+    fn add(parent: &Self::SecretKey, tweak: &[u8]) -> Option<Self::SecretKey> {
+        let mut key = *parent;
+        key.add_assign(tweak).ok()?;
+        Some(key)
+    }
+
+    fn to_bytes(key: &Self::SecretKey) -> Zeroizing<[u8; 32]> {
+        let mut bytes = Zeroizing::new([0; 32]);
+        bytes.copy_from_slice(&key[..]);
+        bytes
+    }
+
+    fn public_key(key: &Self::SecretKey) -> [u8; 33] {
+        secp256k1::PublicKey::from_secret_key(&secp256k1::Secp256k1::signing_only(), key)
+            .serialize()
+    }
+}
+
+struct P256;
+
+impl Curve for P256 {
+    const SEED_KEY: &'static [u8] = b"Nist256p1 seed";
+
+    type SecretKey = p256::SecretKey;
+
+    fn parse(bytes: &[u8]) -> Option<Self::SecretKey> {
+        p256::SecretKey::from_slice(bytes).ok()
+    }
+
+    fn add(parent: &Self::SecretKey, tweak: &[u8]) -> Option<Self::SecretKey> {
+        let mut repr = p256::FieldBytes::default();
+        repr.copy_from_slice(tweak);
+        let tweak = Option::<p256::Scalar>::from(p256::Scalar::from_repr(repr))?;
+        let key = Option::<p256::NonZeroScalar>::from(p256::NonZeroScalar::new(
+            *parent.to_nonzero_scalar() + tweak,
+        ))?;
+        Some(key.into())
+    }
+
+    fn to_bytes(key: &Self::SecretKey) -> Zeroizing<[u8; 32]> {
+        let mut bytes = Zeroizing::new([0; 32]);
+        bytes.copy_from_slice(&key.to_bytes());
+        bytes
+    }
+
+    fn public_key(key: &Self::SecretKey) -> [u8; 33] {
+        let mut bytes = [0; 33];
+        bytes.copy_from_slice(key.public_key().to_encoded_point(true).as_bytes());
+        bytes
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> Zeroizing<[u8; 64]> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for data in data {
+        mac.update(data);
+    }
+    let mut out = Zeroizing::new([0; 64]);
+    out.copy_from_slice(&mac.finalize().into_bytes());
+    out
+}
+
+fn derive<C: Curve>(seed: &[u8], path: &DerivationPath) -> Result<C::SecretKey, Error> {
+    let mut i = hmac_sha512(C::SEED_KEY, &[seed]);
+
+    // Retries with the previous output if the master key is invalid.
+    let mut tries = 0;
+    let mut key = loop {
+        if let Some(key) = C::parse(&i[..32]) {
+            break key;
+        }
+
+        tries += 1;
+        if tries > 8 {
+            return Err(Error::InvalidSeed);
+        }
+
+        i = hmac_sha512(C::SEED_KEY, &[&i[..]]);
+    };
+    let mut chain_code = Zeroizing::new([0; 32]);
+    chain_code.copy_from_slice(&i[32..]);
+
+    for &child in path.children() {
+        let index = child.to_be_bytes();
+
+        i = if child & HARDENED != 0 {
+            hmac_sha512(&*chain_code, &[&[0], &*C::to_bytes(&key), &index])
+        } else {
+            hmac_sha512(&*chain_code, &[&C::public_key(&key), &index])
+        };
+
+        // Retries with different data if the child key is invalid.
+        key = loop {
+            if let Some(child_key) = C::add(&key, &i[..32]) {
+                break child_key;
+            }
+
+            i = hmac_sha512(&*chain_code, &[&[1], &i[32..], &index]);
+        };
+        chain_code.copy_from_slice(&i[32..]);
+    }
+
+    Ok(key)
+}
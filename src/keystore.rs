@@ -0,0 +1,390 @@
+//! Encrypted on-disk storage of secret keys.
+//!
+//! A [`Keystore`] holds one secret key, encrypted with a key derived from a password, along with
+//! the public information needed to log in to the account: its address, the index of the key and
+//! the algorithms of the key.
+//!
+//! The secret key is derived with scrypt and encrypted with ChaCha20-Poly1305. The public
+//! information is authenticated as well, so it cannot be changed without knowing the password.
+//!
+//! ```rust,no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! use flow_sdk::algorithms::{AnyHashAlgorithm, AnySignatureAlgorithm};
+//! use flow_sdk::client::TonicHyperFlowClient;
+//! use flow_sdk::keystore::{KdfParams, Keystore, KeystoreMetadata};
+//!
+//! let metadata = KeystoreMetadata {
+//!     address: hex::decode("f8d6e0586b0a20c7")?.into(),
+//!     key_index: 0,
+//!     sign_algo: AnySignatureAlgorithm::Secp256k1,
+//!     hash_algo: AnyHashAlgorithm::Sha3,
+//! };
+//! let secret_key = hex::decode(std::env::var("SECRET_KEY")?)?;
+//!
+//! let keystore = Keystore::encrypt(&secret_key, metadata, "password", KdfParams::default())?;
+//! keystore.save("service-account.json")?;
+//!
+//! let keystore = Keystore::load("service-account.json")?;
+//! let client = TonicHyperFlowClient::testnet().await?;
+//! let account = keystore.unlock(client, "password").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use chacha20poly1305::aead::{Aead, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, KeyInit};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::access::{AccountResponse, GetAccountAtLatestBlockRequest};
+use crate::account::AnyAccount;
+use crate::algorithms::{AnyHashAlgorithm, AnySecretKey, AnySignatureAlgorithm};
+use crate::client::GrpcClient;
+pub use crate::error::KeystoreError as Error;
+
+const VERSION: u32 = 1;
+const KDF: &str = "scrypt";
+const CIPHER: &str = "chacha20poly1305";
+const SALT_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// The public information of the key held by a keystore.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeystoreMetadata {
+    /// The address of the account.
+    pub address: Box<[u8]>,
+    /// The index of the key in the account.
+    pub key_index: u32,
+    /// The signature algorithm of the key.
+    pub sign_algo: AnySignatureAlgorithm,
+    /// The hashing algorithm of the key.
+    pub hash_algo: AnyHashAlgorithm,
+}
+
+/// The parameters of scrypt, the key derivation function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KdfParams {
+    /// The base 2 logarithm of the CPU/memory cost.
+    pub log_n: u8,
+    /// The block size.
+    pub r: u32,
+    /// The parallelization.
+    pub p: u32,
+}
+
+impl KdfParams {
+    /// The highest `log_n` accepted, using 1 GiB of memory with `r = 8`.
+    pub const MAX_LOG_N: u8 = 20;
+
+    /// The highest block size accepted.
+    pub const MAX_R: u32 = 32;
+
+    /// The highest parallelization accepted.
+    pub const MAX_P: u32 = 16;
+
+    /// Whether the parameters are within the ceilings, so that deriving a key from a keystore
+    /// file cannot use an unbounded amount of memory or time.
+    pub fn is_bounded(&self) -> bool {
+        self.log_n <= Self::MAX_LOG_N && self.r <= Self::MAX_R && self.p <= Self::MAX_P
+    }
+}
+
+impl Default for KdfParams {
+    /// Uses the parameters recommended for interactive logins: `log_n = 15`, `r = 8` and `p = 1`.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// A secret key encrypted with a password.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keystore {
+    metadata: KeystoreMetadata,
+    params: KdfParams,
+    salt: [u8; SALT_LEN],
+    nonce: [u8; NONCE_LEN],
+    ciphertext: Box<[u8]>,
+}
+
+impl Keystore {
+    /// Encrypts a secret key with a password.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the secret key is not valid for the signature algorithm,
+    /// or if the key derivation parameters are invalid.
+    pub fn encrypt(
+        secret_key: &[u8],
+        metadata: KeystoreMetadata,
+        password: impl AsRef<[u8]>,
+        params: KdfParams,
+    ) -> Result<Self, Error> {
+        // Make sure that the keystore can be unlocked.
+        AnySecretKey::new(
+            metadata.sign_algo.code(),
+            metadata.hash_algo.code(),
+            secret_key,
+        )?;
+
+        let mut salt = [0; SALT_LEN];
+        let mut nonce = [0; NONCE_LEN];
+        getrandom::getrandom(&mut salt).map_err(std::io::Error::from)?;
+        getrandom::getrandom(&mut nonce).map_err(std::io::Error::from)?;
+
+        let key = derive_key(password.as_ref(), &salt, params)?;
+        let ciphertext = ChaCha20Poly1305::new(key.as_ref().into())
+            .encrypt(
+                nonce.as_ref().into(),
+                Payload {
+                    msg: secret_key,
+                    aad: &associated_data(&metadata),
+                },
+            )
+            .map_err(|_| Error::Decryption)?
+            .into_boxed_slice();
+
+        Ok(Self {
+            metadata,
+            params,
+            salt,
+            nonce,
+            ciphertext,
+        })
+    }
+
+    /// Returns the public information of the key held by this keystore.
+    #[inline]
+    pub fn metadata(&self) -> &KeystoreMetadata {
+        &self.metadata
+    }
+
+    /// Returns the parameters used to derive the encryption key from the password.
+    #[inline]
+    pub fn kdf_params(&self) -> KdfParams {
+        self.params
+    }
+
+    /// Decrypts the secret key with a password.
+    pub fn decrypt(&self, password: impl AsRef<[u8]>) -> Result<AnySecretKey, Error> {
+        let key = derive_key(password.as_ref(), &self.salt, self.params)?;
+        let secret_key = ChaCha20Poly1305::new(key.as_ref().into())
+            .decrypt(
+                self.nonce.as_ref().into(),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &associated_data(&self.metadata),
+                },
+            )
+            .map(Zeroizing::new)
+            .map_err(|_| Error::Decryption)?;
+
+        Ok(AnySecretKey::new(
+            self.metadata.sign_algo.code(),
+            self.metadata.hash_algo.code(),
+            &secret_key,
+        )?)
+    }
+
+    /// Decrypts the secret key with a password and logs in to the account.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the password is incorrect, if logging in to the account
+    /// fails, or if the key found in the account has a different index than the one recorded.
+    pub async fn unlock<Client>(
+        &self,
+        client: Client,
+        password: impl AsRef<[u8]>,
+    ) -> Result<AnyAccount<Client>, Error>
+    where
+        Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    {
+        let secret_key = self.decrypt(password)?;
+        let account = AnyAccount::new(client, &*self.metadata.address, secret_key).await?;
+
+        if account.primary_key_id() != self.metadata.key_index {
+            return Err(Error::KeyIndexMismatch {
+                expected: self.metadata.key_index,
+                found: account.primary_key_id(),
+            });
+        }
+
+        Ok(account)
+    }
+
+    /// Serializes this keystore to JSON.
+    pub fn to_json(&self) -> String {
+        let file = KeystoreFile {
+            version: VERSION,
+            address: format!("0x{}", hex::encode(&self.metadata.address)),
+            key_index: self.metadata.key_index,
+            sign_algo: self.metadata.sign_algo.name().into(),
+            hash_algo: self.metadata.hash_algo.name().into(),
+            kdf: KdfFile {
+                function: KDF.into(),
+                log_n: self.params.log_n,
+                r: self.params.r,
+                p: self.params.p,
+                salt: hex::encode(self.salt),
+            },
+            cipher: CipherFile {
+                function: CIPHER.into(),
+                nonce: hex::encode(self.nonce),
+            },
+            ciphertext: hex::encode(&self.ciphertext),
+        };
+
+        serde_json::to_string_pretty(&file).expect("keystores can always be serialized")
+    }
+
+    /// Deserializes a keystore from JSON.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let file: KeystoreFile = serde_json::from_str(json)?;
+
+        if file.version != VERSION {
+            return Err(Error::UnsupportedVersion(file.version));
+        }
+
+        if file.kdf.function != KDF {
+            return Err(Error::Unsupported(
+                "key derivation function",
+                file.kdf.function,
+            ));
+        }
+
+        if file.cipher.function != CIPHER {
+            return Err(Error::Unsupported("cipher", file.cipher.function));
+        }
+
+        let address = hex::decode(file.address.trim_start_matches("0x"))
+            .map_err(|_| Error::InvalidField("address"))?;
+        let sign_algo = AnySignatureAlgorithm::from_name(&file.sign_algo)
+            .ok_or(Error::Unsupported("signature algorithm", file.sign_algo))?;
+        let hash_algo = AnyHashAlgorithm::from_name(&file.hash_algo)
+            .ok_or(Error::Unsupported("hashing algorithm", file.hash_algo))?;
+
+        let params = KdfParams {
+            log_n: file.kdf.log_n,
+            r: file.kdf.r,
+            p: file.kdf.p,
+        };
+        if !params.is_bounded() {
+            return Err(Error::InvalidParams);
+        }
+
+        let mut salt = [0; SALT_LEN];
+        let mut nonce = [0; NONCE_LEN];
+        hex::decode_to_slice(&file.kdf.salt, &mut salt).map_err(|_| Error::InvalidField("salt"))?;
+        hex::decode_to_slice(&file.cipher.nonce, &mut nonce)
+            .map_err(|_| Error::InvalidField("nonce"))?;
+        let ciphertext =
+            hex::decode(&file.ciphertext).map_err(|_| Error::InvalidField("ciphertext"))?;
+
+        Ok(Self {
+            metadata: KeystoreMetadata {
+                address: address.into(),
+                key_index: file.key_index,
+                sign_algo,
+                hash_algo,
+            },
+            params,
+            salt,
+            nonce,
+            ciphertext: ciphertext.into(),
+        })
+    }
+
+    /// Saves this keystore to a file, replacing it if it exists.
+    ///
+    /// On unix, the file is only readable and writable by its owner: new files are created with
+    /// these permissions, and the permissions of existing files are restricted before the keystore
+    /// is written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(path)?;
+
+        // The mode only applies to files that are created.
+        #[cfg(unix)]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+
+        file.write_all(self.to_json().as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Loads a keystore from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreFile {
+    version: u32,
+    address: String,
+    key_index: u32,
+    sign_algo: String,
+    hash_algo: String,
+    kdf: KdfFile,
+    cipher: CipherFile,
+    ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KdfFile {
+    function: String,
+    log_n: u8,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CipherFile {
+    function: String,
+    nonce: String,
+}
+
+fn derive_key(
+    password: &[u8],
+    salt: &[u8],
+    params: KdfParams,
+) -> Result<Zeroizing<[u8; 32]>, Error> {
+    // The parameters come from the keystore file, they are checked before allocating.
+    if !params.is_bounded() {
+        return Err(Error::InvalidParams);
+    }
+
+    let params = scrypt::Params::new(params.log_n, params.r, params.p, 32)
+        .map_err(|_| Error::InvalidParams)?;
+    let mut key = Zeroizing::new([0; 32]);
+    scrypt::scrypt(password, salt, &params, key.as_mut()).map_err(|_| Error::InvalidParams)?;
+    Ok(key)
+}
+
+/// The public information authenticated along with the secret key.
+fn associated_data(metadata: &KeystoreMetadata) -> Vec<u8> {
+    format!(
+        "flow-keystore-v{}|{}|{}|{}|{}",
+        VERSION,
+        hex::encode(&metadata.address),
+        metadata.key_index,
+        metadata.sign_algo.name(),
+        metadata.hash_algo.name()
+    )
+    .into_bytes()
+}
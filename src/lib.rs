@@ -18,11 +18,23 @@ pub mod prelude;
 pub mod access;
 pub mod account;
 pub mod algorithms;
+pub mod cadence_error;
 pub mod client;
 pub mod codec;
+#[cfg(feature = "config")]
+pub mod config;
 pub mod entities;
 pub mod error;
+#[cfg(feature = "hd-wallet")]
+pub mod hd;
+#[cfg(feature = "keystore")]
+pub mod keystore;
+pub mod message;
 pub mod multi;
+#[cfg(feature = "payer-service")]
+pub mod payer;
+pub mod pipeline;
+pub mod proposer;
 pub mod protobuf;
 pub mod requests;
 pub mod sign;
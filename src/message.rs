@@ -0,0 +1,182 @@
+//! Sign and verify messages outside of transactions.
+//!
+//! Messages signed with [`Account::sign_user_message`] can be verified with
+//! `verify_user_signatures`, and account proofs signed with [`Account::sign_account_proof`] can be
+//! verified with `verify_account_proof`. Both check the signatures against the keys of the account
+//! on chain, and are behind the `any-algorithms` feature.
+//!
+//! [`Account::sign_user_message`]: crate::account::Account::sign_user_message
+//! [`Account::sign_account_proof`]: crate::account::Account::sign_account_proof
+
+#[cfg(feature = "any-algorithms")]
+use std::collections::HashSet;
+
+use rlp::RlpStream;
+
+#[cfg(feature = "any-algorithms")]
+use crate::access::{AccountResponse, GetAccountAtLatestBlockRequest};
+#[cfg(feature = "any-algorithms")]
+use crate::account::{PADDED_ACCOUNT_PROOF_DOMAIN_TAG, PADDED_USER_DOMAIN_TAG};
+#[cfg(feature = "any-algorithms")]
+use crate::client::{FlowClient, GrpcClient};
+#[cfg(feature = "any-algorithms")]
+use crate::transaction::SignatureE;
+
+#[cfg(feature = "any-algorithms")]
+pub use crate::error::VerifySignaturesError as Error;
+
+/// The total weight that the keys must reach for the signatures to be valid.
+pub const FULL_WEIGHT: u32 = 1000;
+
+/// The minimum length of the nonce of an account proof.
+pub const MIN_NONCE_LEN: usize = 32;
+
+/// Encodes the message of an FCL account proof, excluding the domain tag: the RLP encoding of the
+/// app identifier, the address and the nonce.
+///
+/// # Panics
+///
+/// Panics if the address is longer than 8 bytes, `verify_account_proof` checks it first.
+pub fn encode_account_proof(
+    app_identifier: &str,
+    address: impl AsRef<[u8]>,
+    nonce: impl AsRef<[u8]>,
+) -> Vec<u8> {
+    let address = address.as_ref();
+    let mut padded_address = [0; 8];
+    padded_address[8 - address.len()..].copy_from_slice(address);
+
+    let mut stream = RlpStream::new_list(3);
+    stream
+        .append(&app_identifier.as_bytes())
+        .append(&&padded_address[..])
+        .append(&nonce.as_ref());
+    stream.out().to_vec()
+}
+
+/// Verifies signatures of a message made with the user domain tag by the keys of an account.
+///
+/// Each key can sign only once, and the keys must have a total weight of at least
+/// [`FULL_WEIGHT`].
+///
+/// # Errors
+///
+/// This function returns an error if:
+///
+///  - the client returns any errors while making requests
+///  - a signature was made by another account, or by a key that is not found
+///  - a signature could not be verified with its key, or its key is revoked
+///  - the keys do not have enough weight in total.
+#[cfg(feature = "any-algorithms")]
+pub async fn verify_user_signatures<Client, Address, Signature>(
+    client: &mut FlowClient<Client>,
+    address: &[u8],
+    message: impl AsRef<[u8]>,
+    signatures: impl IntoIterator<Item = SignatureE<Address, Signature>>,
+) -> Result<(), Error>
+where
+    Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    Address: AsRef<[u8]>,
+    Signature: AsRef<[u8]>,
+{
+    let mut data = PADDED_USER_DOMAIN_TAG.to_vec();
+    data.extend_from_slice(message.as_ref());
+
+    verify_signatures(client, address, &data, signatures).await
+}
+
+/// Verifies the signatures of an FCL account proof made by the keys of an account.
+///
+/// Each key can sign only once, and the keys must have a total weight of at least
+/// [`FULL_WEIGHT`].
+///
+/// # Errors
+///
+/// This function returns an error if:
+///
+///  - the address is longer than 8 bytes
+///  - the nonce is shorter than [`MIN_NONCE_LEN`]
+///  - the client returns any errors while making requests
+///  - a signature was made by another account, or by a key that is not found
+///  - a signature could not be verified with its key, or its key is revoked
+///  - the keys do not have enough weight in total.
+#[cfg(feature = "any-algorithms")]
+pub async fn verify_account_proof<Client, Address, Signature>(
+    client: &mut FlowClient<Client>,
+    app_identifier: &str,
+    address: &[u8],
+    nonce: impl AsRef<[u8]>,
+    signatures: impl IntoIterator<Item = SignatureE<Address, Signature>>,
+) -> Result<(), Error>
+where
+    Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    Address: AsRef<[u8]>,
+    Signature: AsRef<[u8]>,
+{
+    if address.len() > 8 {
+        return Err(Error::InvalidAddress);
+    }
+
+    if nonce.as_ref().len() < MIN_NONCE_LEN {
+        return Err(Error::NonceTooShort);
+    }
+
+    let mut data = PADDED_ACCOUNT_PROOF_DOMAIN_TAG.to_vec();
+    data.extend_from_slice(&encode_account_proof(app_identifier, address, nonce));
+
+    verify_signatures(client, address, &data, signatures).await
+}
+
+/// Verifies signatures of data, which includes its domain tag, made by the keys of an account.
+#[cfg(feature = "any-algorithms")]
+async fn verify_signatures<Client, Address, Signature>(
+    client: &mut FlowClient<Client>,
+    address: &[u8],
+    data: &[u8],
+    signatures: impl IntoIterator<Item = SignatureE<Address, Signature>>,
+) -> Result<(), Error>
+where
+    Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    Address: AsRef<[u8]>,
+    Signature: AsRef<[u8]>,
+{
+    let account = client
+        .account_at_latest_block(address)
+        .await
+        .map_err(Into::into)?;
+
+    let mut signed = HashSet::new();
+    let mut weight = 0;
+
+    for SignatureE {
+        address: signer,
+        key_id,
+        signature,
+    } in signatures
+    {
+        if signer.as_ref() != address {
+            return Err(Error::AddressMismatch);
+        }
+
+        if !signed.insert(key_id) {
+            return Err(Error::DuplicateKey(key_id));
+        }
+
+        let key = account
+            .keys
+            .iter()
+            .find(|key| key.index == key_id)
+            .ok_or(Error::KeyNotFound(key_id))?;
+
+        key.verify(data, signature.as_ref())
+            .map_err(|error| Error::InvalidSignature { key_id, error })?;
+
+        weight += key.weight;
+    }
+
+    if weight < FULL_WEIGHT {
+        return Err(Error::NotEnoughWeight(weight));
+    }
+
+    Ok(())
+}
@@ -9,9 +9,18 @@
 //! [`PreHashedParty`] computes and stores the payload hash so it does not need to be recomputed.
 //!
 //! Both party types implement the common interface, the [`Party`] trait.
+//!
+//! [`interchange`] moves parties between machines, for signing them offline, and [`fcl`] signs
+//! them for wallets and FCL.
+//!
+//! `session::SigningSession` collects and verifies the signatures of a party, keeping track of
+//! who still has to sign.
 
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
 
 use rlp::RlpStream;
 
@@ -20,7 +29,7 @@ use crate::access::{
     GetLatestBlockHeaderRequest,
 };
 use crate::account::PADDED_TRANSACTION_DOMAIN_TAG;
-use crate::algorithms::{FlowHasher, FlowSigner};
+use crate::algorithms::{AsyncFlowSigner, FlowHasher};
 use crate::client::GrpcClient;
 use crate::error::BoxError;
 use crate::prelude::Account;
@@ -28,6 +37,11 @@ use crate::protobuf::Seal;
 use crate::transaction::rlp::{rlp_encode_transaction_envelope, rlp_encode_transaction_payload};
 use crate::transaction::{ProposalKeyE, SignatureE, TransactionE};
 
+pub mod fcl;
+pub mod interchange;
+#[cfg(feature = "any-algorithms")]
+pub mod session;
+
 /// The `Party` trait. You can get information about the transaction you are signing and sign it by
 /// accepting some type that implements this trait.
 ///
@@ -105,6 +119,33 @@ pub type PartyTransaction<SigAddr, Sig> = TransactionE<
     Vec<SignatureE<SigAddr, Sig>>,
 >;
 
+/// The future returned by the methods of [`PartySigner`].
+pub type PartySignFuture<'a, T> =
+    Pin<Box<dyn Future<Output = Result<T, Box<dyn Error + Send + Sync>>> + 'a>>;
+
+/// Signs parties on behalf of an account, so that other accounts can take part in transactions
+/// sent with [`Account::send_transaction_header_with`].
+///
+/// Unlike [`Party`], this trait is object safe. It signs asynchronously, and is implemented for
+/// every [`Account`], including the ones whose keys are held by an [`AsyncFlowSigner`].
+///
+/// [`Account::send_transaction_header_with`]: crate::account::Account::send_transaction_header_with
+/// [`AsyncFlowSigner`]: crate::algorithms::AsyncFlowSigner
+pub trait PartySigner {
+    /// Returns the address of the account.
+    fn address(&self) -> &[u8];
+
+    /// Signs the payload of the party, as a proposer or an authorizer.
+    fn sign_payload<'a>(&'a self, party: &'a mut SigningParty) -> PartySignFuture<'a, ()>;
+
+    /// Creates signatures of the envelope of the party, as the payer.
+    #[allow(clippy::type_complexity)]
+    fn sign_envelope<'a>(
+        &'a self,
+        party: &'a SigningParty,
+    ) -> PartySignFuture<'a, Vec<SignatureE<Box<[u8]>, [u8; 64]>>>;
+}
+
 /// A builder that makes it easy to create new [`SigningParty`] instances.
 ///
 /// ```
@@ -246,7 +287,7 @@ impl PartyBuilder {
     ) -> Result<Self, BoxError>
     where
         C: GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
-        Sign: FlowSigner<SecretKey = Sk>,
+        Sign: AsyncFlowSigner<SecretKey = Sk>,
         Hash: FlowHasher,
     {
         self.proposer_address = Some(acc.address().into());
@@ -383,20 +424,7 @@ impl<H: FlowHasher> Party<H> for SigningParty {
     fn payload(&self) -> H {
         let mut hasher = H::new();
         hasher.update(&PADDED_TRANSACTION_DOMAIN_TAG);
-        let mut stream = RlpStream::new();
-        rlp_encode_transaction_payload(
-            &mut stream,
-            &*self.script,
-            self.arguments.iter(),
-            self.reference_block.iter(),
-            self.gas_limit,
-            &self.proposer_address,
-            self.proposal_key_id,
-            self.proposal_key_sequence_number,
-            &self.payer,
-            self.authorizers.iter(),
-        );
-        hasher.update(&stream.out());
+        hasher.update(&self.encode_payload());
 
         hasher
     }
@@ -417,6 +445,26 @@ impl<H: FlowHasher> Party<H> for SigningParty {
     fn envelope(&self) -> H {
         let mut hasher = H::new();
         hasher.update(&PADDED_TRANSACTION_DOMAIN_TAG);
+        hasher.update(&self.encode_envelope());
+        hasher
+    }
+
+    fn into_transaction_with_envelope_signatures<SigAddr, Sig>(
+        self,
+        signatures: impl IntoIterator<Item = SignatureE<SigAddr, Sig>>,
+    ) -> PartyTransaction<SigAddr, Sig> {
+        self.into_transaction(signatures)
+    }
+}
+
+impl SigningParty {
+    fn encode_payload(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        self.encode_payload_to(&mut stream);
+        stream.out().to_vec()
+    }
+
+    fn encode_envelope(&self) -> Vec<u8> {
         let mut stream = RlpStream::new();
         rlp_encode_transaction_envelope(
             &mut stream,
@@ -437,11 +485,25 @@ impl<H: FlowHasher> Party<H> for SigningParty {
                 )
             }),
         );
-        hasher.update(&stream.out());
-        hasher
+        stream.out().to_vec()
     }
 
-    fn into_transaction_with_envelope_signatures<SigAddr, Sig>(
+    fn encode_payload_to(&self, stream: &mut RlpStream) {
+        rlp_encode_transaction_payload(
+            stream,
+            &*self.script,
+            self.arguments.iter(),
+            self.reference_block.iter(),
+            self.gas_limit,
+            &self.proposer_address,
+            self.proposal_key_id,
+            self.proposal_key_sequence_number,
+            &self.payer,
+            self.authorizers.iter(),
+        );
+    }
+
+    pub(crate) fn into_transaction<SigAddr, Sig>(
         self,
         signatures: impl IntoIterator<Item = SignatureE<SigAddr, Sig>>,
     ) -> PartyTransaction<SigAddr, Sig> {
@@ -518,6 +580,12 @@ impl SigningParty {
         }
     }
 
+    /// Returns the payload signatures collected so far.
+    #[inline]
+    pub fn payload_signatures(&self) -> &[SignatureE<Box<[u8]>, [u8; 64]>] {
+        &self.payload_signatures
+    }
+
     /// Computes the payload hash of this party, and turns this into a [`PreHashedParty`].
     pub fn into_prehashed<H: FlowHasher>(self) -> PreHashedParty<H> {
         let payload = self.payload();
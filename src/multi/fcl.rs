@@ -0,0 +1,559 @@
+//! The authorization protocol of the Flow Client Library (FCL).
+//!
+//! Wallets and FCL ask custodial signers to sign transactions over HTTP with JSON bodies. During
+//! pre-authorization, the signer answers with the [`Service`]s that will sign each role. During
+//! authorization, each service receives a [`Signable`] holding the transaction as a [`Voucher`],
+//! and answers with a [`CompositeSignature`].
+//!
+//! [`AuthzService`] answers both requests on behalf of an account. Vouchers convert to and from
+//! [`SigningParty`] and [`PortableParty`], to sign them with the rest of this crate.
+//!
+//! ```rust,no_run
+//! # use flow_sdk::multi::PartySigner;
+//! # use flow_sdk::multi::fcl::{AuthzService, Error};
+//! # async fn run(account: impl PartySigner, pre_authz: &str, authz: &str) -> Result<(), Error> {
+//! let service = AuthzService::new(account, 0, "https://example.com/authz");
+//!
+//! // The body of a request to the pre-authorization endpoint.
+//! let services = service.pre_authz_json(pre_authz)?;
+//!
+//! // The body of a request to "https://example.com/authz".
+//! let signature = service.authz_json(authz).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+
+use super::interchange::PortableParty;
+use super::{PartySigner, SigningParty};
+use crate::account::PADDED_TRANSACTION_DOMAIN_TAG;
+use crate::error::InterchangeError;
+use crate::transaction::SignatureE;
+
+pub use crate::error::FclError as Error;
+
+type Signature = SignatureE<Box<[u8]>, [u8; 64]>;
+
+/// The version of the FCL objects created by this module.
+pub const FCL_VERSION: &str = "1.0.0";
+
+/// A transaction, in the format used by FCL.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Voucher {
+    /// The script of the transaction.
+    pub cadence: String,
+    /// The hex of the reference block ID.
+    pub ref_block: String,
+    /// The gas limit.
+    pub compute_limit: u64,
+    /// The arguments, encoded in JSON-Cadence.
+    ///
+    /// They are kept as they were received, since the payload is signed over their exact bytes.
+    pub arguments: Vec<Box<RawValue>>,
+    /// The proposal key.
+    pub proposal_key: VoucherProposalKey,
+    /// The address of the payer.
+    pub payer: String,
+    /// The addresses of the authorizers.
+    pub authorizers: Vec<String>,
+    /// The payload signatures.
+    pub payload_sigs: Vec<VoucherSignature>,
+    /// The envelope signatures.
+    pub envelope_sigs: Vec<VoucherSignature>,
+}
+
+/// The proposal key of a [`Voucher`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoucherProposalKey {
+    /// The address of the proposer.
+    pub address: String,
+    /// The index of the key.
+    pub key_id: u64,
+    /// The sequence number of the key.
+    pub sequence_num: u64,
+}
+
+/// A signature of a [`Voucher`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoucherSignature {
+    /// The address of the account that signs.
+    pub address: String,
+    /// The index of the key.
+    pub key_id: u32,
+    /// The hex of the signature, if it has been made yet.
+    pub sig: Option<String>,
+}
+
+/// The roles of an account in a transaction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Roles {
+    /// Whether the account is the proposer.
+    #[serde(default)]
+    pub proposer: bool,
+    /// Whether the account is an authorizer.
+    #[serde(default)]
+    pub authorizer: bool,
+    /// Whether the account is the payer.
+    #[serde(default)]
+    pub payer: bool,
+}
+
+/// A pre-authorization request, asking which services will sign each role.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "f_type", rename = "PreSignable")]
+pub struct PreSignable {
+    /// The roles that the wallet is asked to sign.
+    pub roles: Roles,
+    /// The script of the transaction.
+    pub cadence: String,
+    /// The transaction, as built so far.
+    pub voucher: Voucher,
+}
+
+/// An authorization request, asking an account to sign a transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "f_type", rename = "Signable")]
+pub struct Signable {
+    /// The hex of the message to sign: the payload, or the envelope if the account is the payer.
+    pub message: String,
+    /// The address of the account asked to sign.
+    pub addr: String,
+    /// The index of the key asked to sign.
+    #[serde(rename = "keyId")]
+    pub key_id: u32,
+    /// The roles of the account.
+    pub roles: Roles,
+    /// The script of the transaction.
+    pub cadence: String,
+    /// The transaction.
+    pub voucher: Voucher,
+}
+
+/// A signature made in answer to a [`Signable`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "f_type", rename = "CompositeSignature")]
+pub struct CompositeSignature {
+    /// The version of this object.
+    pub f_vsn: String,
+    /// The address of the account that signed.
+    pub addr: String,
+    /// The index of the key.
+    #[serde(rename = "keyId")]
+    pub key_id: u32,
+    /// The hex of the signature.
+    pub signature: String,
+}
+
+/// An endpoint that signs on behalf of an account.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "f_type", rename = "Service")]
+pub struct Service {
+    /// The version of this object.
+    pub f_vsn: String,
+    /// The type of the service, `authz` for signing transactions.
+    #[serde(rename = "type")]
+    pub service_type: String,
+    /// The URL of the endpoint.
+    pub endpoint: String,
+    /// How the endpoint is called, such as `HTTP/POST`.
+    pub method: String,
+    /// The account and key that sign.
+    pub identity: Identity,
+}
+
+/// The account and key of a [`Service`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "f_type", rename = "Identity")]
+pub struct Identity {
+    /// The version of this object.
+    pub f_vsn: String,
+    /// The address of the account.
+    pub address: String,
+    /// The index of the key.
+    #[serde(rename = "keyId")]
+    pub key_id: u32,
+}
+
+/// The services that sign each role of a transaction, in answer to a [`PreSignable`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "f_type", rename = "PreAuthzResponse")]
+pub struct PreAuthzResponse {
+    /// The service that signs as the proposer.
+    pub proposer: Option<Service>,
+    /// The services that sign as the payer.
+    pub payer: Vec<Service>,
+    /// The services that sign as authorizers.
+    pub authorization: Vec<Service>,
+}
+
+/// The status of a [`PollingResponse`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PollingStatus {
+    /// The request was approved.
+    Approved,
+    /// The request was declined.
+    Declined,
+    /// The request is not answered yet.
+    Pending,
+}
+
+/// The answer to a request of FCL.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "f_type", rename = "PollingResponse")]
+pub struct PollingResponse<T> {
+    /// The version of this object.
+    pub f_vsn: String,
+    /// The status of the request.
+    pub status: PollingStatus,
+    /// Why the request was declined.
+    pub reason: Option<String>,
+    /// The answer, if the request was approved.
+    pub data: Option<T>,
+}
+
+impl Signable {
+    /// Creates a request for an account to sign a party, with the key specified.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the party could not be converted into a voucher, or if
+    /// the account does not take part in the transaction.
+    pub fn new(party: &PortableParty, address: &[u8], key_id: u32) -> Result<Self, Error> {
+        let signing_party = party.party();
+        let roles = Roles {
+            proposer: *signing_party.proposer_address == *address,
+            authorizer: signing_party
+                .authorizers
+                .iter()
+                .any(|authorizer| **authorizer == *address),
+            payer: *signing_party.payer == *address,
+        };
+
+        if roles == Roles::default() {
+            return Err(InterchangeError::UnknownSigner.into());
+        }
+
+        Ok(Self {
+            message: hex::encode(message(signing_party, roles.payer)),
+            addr: encode_address(address),
+            key_id,
+            roles,
+            cadence: signing_party.script.to_string(),
+            voucher: Voucher::try_from(party)?,
+        })
+    }
+}
+
+impl<T> PollingResponse<T> {
+    /// Creates an approved response.
+    pub fn approved(data: T) -> Self {
+        Self {
+            f_vsn: FCL_VERSION.into(),
+            status: PollingStatus::Approved,
+            reason: None,
+            data: Some(data),
+        }
+    }
+
+    /// Creates a declined response.
+    pub fn declined(reason: impl Into<String>) -> Self {
+        Self {
+            f_vsn: FCL_VERSION.into(),
+            status: PollingStatus::Declined,
+            reason: Some(reason.into()),
+            data: None,
+        }
+    }
+}
+
+impl TryFrom<&PortableParty> for Voucher {
+    type Error = Error;
+
+    /// Converts a party and its signatures.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if an argument is not valid JSON.
+    fn try_from(party: &PortableParty) -> Result<Self, Self::Error> {
+        let mut voucher = Voucher::try_from(party.party())?;
+        voucher.envelope_sigs = party.envelope_signatures().iter().map(Into::into).collect();
+        Ok(voucher)
+    }
+}
+
+impl TryFrom<&SigningParty> for Voucher {
+    type Error = Error;
+
+    /// Converts a party and its payload signatures.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if an argument is not valid JSON.
+    fn try_from(party: &SigningParty) -> Result<Self, Self::Error> {
+        let arguments = party
+            .arguments
+            .iter()
+            .map(|argument| {
+                let argument =
+                    std::str::from_utf8(argument).map_err(|_| Error::InvalidField("arguments"))?;
+                RawValue::from_string(argument.to_owned()).map_err(Error::Json)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            cadence: party.script.to_string(),
+            ref_block: hex::encode(&party.reference_block),
+            compute_limit: party.gas_limit,
+            arguments,
+            proposal_key: VoucherProposalKey {
+                address: encode_address(&party.proposer_address),
+                key_id: party.proposal_key_id,
+                sequence_num: party.proposal_key_sequence_number,
+            },
+            payer: encode_address(&party.payer),
+            authorizers: party.authorizers.iter().map(encode_address).collect(),
+            payload_sigs: party.payload_signatures.iter().map(Into::into).collect(),
+            envelope_sigs: Vec::new(),
+        })
+    }
+}
+
+impl TryFrom<&Voucher> for PortableParty {
+    type Error = Error;
+
+    /// Converts a voucher and the signatures made so far.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a field is not valid hex, or if a signature is made by
+    /// an account that does not sign in its role.
+    fn try_from(voucher: &Voucher) -> Result<Self, Self::Error> {
+        let arguments = voucher
+            .arguments
+            .iter()
+            .map(|argument| argument.get().as_bytes().into())
+            .collect();
+        let authorizers = voucher
+            .authorizers
+            .iter()
+            .map(|authorizer| decode_hex(authorizer, "authorizers"))
+            .collect::<Result<_, _>>()?;
+
+        let party = SigningParty::new(
+            voucher.cadence.as_str().into(),
+            arguments,
+            decode_hex(&voucher.ref_block, "refBlock")?,
+            voucher.compute_limit,
+            decode_hex(&voucher.proposal_key.address, "proposalKey.address")?,
+            voucher.proposal_key.key_id,
+            voucher.proposal_key.sequence_num,
+            decode_hex(&voucher.payer, "payer")?,
+            authorizers,
+        );
+
+        Ok(PortableParty::with_signatures(
+            party,
+            decode_signatures(&voucher.payload_sigs)?,
+            decode_signatures(&voucher.envelope_sigs)?,
+        )?)
+    }
+}
+
+impl TryFrom<&Voucher> for SigningParty {
+    type Error = Error;
+
+    /// Converts a voucher and its payload signatures. Envelope signatures are dropped.
+    fn try_from(voucher: &Voucher) -> Result<Self, Self::Error> {
+        PortableParty::try_from(voucher).map(PortableParty::into_party)
+    }
+}
+
+/// Answers the pre-authorization and authorization requests of FCL on behalf of an account.
+#[derive(Debug)]
+pub struct AuthzService<Signer> {
+    signer: Signer,
+    key_id: u32,
+    endpoint: String,
+}
+
+impl<Signer: PartySigner> AuthzService<Signer> {
+    /// Creates a service that signs with the key specified, and that is called at the endpoint
+    /// specified for authorization requests.
+    pub fn new(signer: Signer, key_id: u32, endpoint: impl Into<String>) -> Self {
+        Self {
+            signer,
+            key_id,
+            endpoint: endpoint.into(),
+        }
+    }
+
+    /// Returns the account that signs.
+    #[inline]
+    pub fn signer(&self) -> &Signer {
+        &self.signer
+    }
+
+    /// Returns the service of this account, which FCL calls to sign transactions.
+    pub fn service(&self) -> Service {
+        Service {
+            f_vsn: FCL_VERSION.into(),
+            service_type: "authz".into(),
+            endpoint: self.endpoint.clone(),
+            method: "HTTP/POST".into(),
+            identity: Identity {
+                f_vsn: FCL_VERSION.into(),
+                address: encode_address(self.signer.address()),
+                key_id: self.key_id,
+            },
+        }
+    }
+
+    /// Answers a pre-authorization request with the service of this account, for every role that
+    /// it is asked to sign.
+    pub fn pre_authz(&self, pre_signable: &PreSignable) -> PollingResponse<PreAuthzResponse> {
+        let roles = pre_signable.roles;
+        let services = |signs| {
+            if signs {
+                vec![self.service()]
+            } else {
+                Vec::new()
+            }
+        };
+
+        PollingResponse::approved(PreAuthzResponse {
+            proposer: roles.proposer.then(|| self.service()),
+            payer: services(roles.payer),
+            authorization: services(roles.authorizer),
+        })
+    }
+
+    /// Answers an authorization request, signing the envelope if this account is the payer and
+    /// the payload otherwise.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    ///  - the voucher could not be converted, see [`PortableParty`]'s conversion
+    ///  - this account is not the one asked to sign, or does not take part in the transaction
+    ///  - the message is not the payload or the envelope of the voucher
+    ///  - this account could not sign, or did not sign with the key asked.
+    pub async fn authz(
+        &self,
+        signable: &Signable,
+    ) -> Result<PollingResponse<CompositeSignature>, Error> {
+        let address = self.signer.address();
+
+        if decode_hex(&signable.addr, "addr")?[..] != *address {
+            return Err(Error::WrongSigner);
+        }
+
+        let mut party = PortableParty::try_from(&signable.voucher)?;
+        let is_payer = party.party().payer[..] == *address;
+
+        // Sign what the voucher encodes, never the message as it was sent.
+        if decode_hex(&signable.message, "message")?[..] != message(party.party(), is_payer)[..] {
+            return Err(Error::MessageMismatch);
+        }
+
+        let signed = if is_payer {
+            let signed = party.envelope_signatures().len();
+            party.sign(&self.signer).await?;
+            party.envelope_signatures()[signed..].to_vec()
+        } else {
+            let signed = party.party().payload_signatures.len();
+            party.sign(&self.signer).await?;
+            party.party().payload_signatures[signed..].to_vec()
+        };
+
+        let signature = signed
+            .into_iter()
+            .find(|signature| signature.key_id == signable.key_id)
+            .ok_or(Error::KeyNotFound(signable.key_id))?;
+
+        Ok(PollingResponse::approved(CompositeSignature {
+            f_vsn: FCL_VERSION.into(),
+            addr: encode_address(address),
+            key_id: signature.key_id,
+            signature: hex::encode(signature.signature),
+        }))
+    }
+
+    /// Answers the JSON body of a pre-authorization request, see [`AuthzService::pre_authz`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the request is not a valid [`PreSignable`].
+    pub fn pre_authz_json(&self, json: &str) -> Result<String, Error> {
+        let pre_signable = serde_json::from_str(json)?;
+        Ok(serde_json::to_string(&self.pre_authz(&pre_signable))?)
+    }
+
+    /// Answers the JSON body of an authorization request, see [`AuthzService::authz`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the request is not a valid [`Signable`], or if it could
+    /// not be signed.
+    pub async fn authz_json(&self, json: &str) -> Result<String, Error> {
+        let signable = serde_json::from_str(json)?;
+        Ok(serde_json::to_string(&self.authz(&signable).await?)?)
+    }
+}
+
+impl From<&Signature> for VoucherSignature {
+    fn from(signature: &Signature) -> Self {
+        Self {
+            address: encode_address(&signature.address),
+            key_id: signature.key_id,
+            sig: Some(hex::encode(signature.signature)),
+        }
+    }
+}
+
+/// The message that the payer signs, or the other accounts.
+fn message(party: &SigningParty, is_payer: bool) -> Vec<u8> {
+    let mut message = PADDED_TRANSACTION_DOMAIN_TAG.to_vec();
+    if is_payer {
+        message.extend_from_slice(&party.encode_envelope());
+    } else {
+        message.extend_from_slice(&party.encode_payload());
+    }
+    message
+}
+
+fn encode_address(address: impl AsRef<[u8]>) -> String {
+    format!("0x{}", hex::encode(address))
+}
+
+fn decode_hex(s: &str, field: &'static str) -> Result<Box<[u8]>, Error> {
+    hex::decode(s.trim_start_matches("0x"))
+        .map(Into::into)
+        .map_err(|_| Error::InvalidField(field))
+}
+
+/// Decodes the signatures that have been made, skipping the ones that are still pending.
+fn decode_signatures(signatures: &[VoucherSignature]) -> Result<Vec<Signature>, Error> {
+    let mut decoded = Vec::new();
+
+    for signature in signatures {
+        let sig = match &signature.sig {
+            Some(sig) => decode_hex(sig, "sig")?,
+            None => continue,
+        };
+
+        decoded.push(SignatureE {
+            address: decode_hex(&signature.address, "address")?,
+            key_id: signature.key_id,
+            signature: (*sig).try_into().map_err(|_| Error::InvalidField("sig"))?,
+        });
+    }
+
+    Ok(decoded)
+}
@@ -0,0 +1,534 @@
+//! Portable formats of parties, for signing transactions on other machines.
+//!
+//! A [`PortableParty`] holds a party and the signatures collected so far. It can be exported to
+//! versioned JSON, or to the hex of the RLP encoding of the transaction used by the Flow CLI, and
+//! imported on another machine. The signed copies are then merged back together.
+//!
+//! Transactions built elsewhere, such as a [`TransactionD`] returned by the network or the RLP
+//! encoding of a transaction made by another SDK, can be converted into parties as well, keeping
+//! their payload signatures so that more accounts can sign them.
+//!
+//! ```rust,no_run
+//! # use flow_sdk::multi::SigningParty;
+//! # use flow_sdk::multi::interchange::PortableParty;
+//! # use flow_sdk::multi::PartySigner;
+//! # async fn run(
+//! #     party: SigningParty,
+//! #     authorizer: &dyn PartySigner,
+//! #     payer: &dyn PartySigner,
+//! # ) -> Result<(), flow_sdk::multi::interchange::Error> {
+//! // On the machine that builds the transaction.
+//! let exported = PortableParty::new(party).export_json();
+//!
+//! // On the machine of the authorizer.
+//! let mut party = PortableParty::import_json(&exported)?;
+//! party.sign(authorizer).await?;
+//! let signed = party.export_json();
+//!
+//! // Back on the first machine, the payer signs the envelope last.
+//! let mut party = PortableParty::import_json(&exported)?;
+//! party.merge(PortableParty::import_json(&signed)?)?;
+//! party.sign(payer).await?;
+//! let transaction = party.into_transaction();
+//! # Ok(())
+//! # }
+//! ```
+
+use otopr::Repeated;
+use rlp::{Rlp, RlpStream};
+use serde::{Deserialize, Serialize};
+
+use super::{PartySigner, PartyTransaction, PreHashedParty, SigningParty};
+use crate::algorithms::FlowHasher;
+use crate::transaction::{SignatureD, SignatureE, TransactionD};
+
+pub use crate::error::InterchangeError as Error;
+
+const VERSION: u32 = 1;
+
+type Signature = SignatureE<Box<[u8]>, [u8; 64]>;
+
+/// A party and the signatures collected for it, which can be moved between machines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PortableParty {
+    party: SigningParty,
+    envelope_signatures: Vec<Signature>,
+}
+
+impl PortableParty {
+    /// Creates a portable party without envelope signatures.
+    #[inline]
+    pub fn new(party: SigningParty) -> Self {
+        Self {
+            party,
+            envelope_signatures: Vec::new(),
+        }
+    }
+
+    /// Returns the party, with the payload signatures collected so far.
+    #[inline]
+    pub fn party(&self) -> &SigningParty {
+        &self.party
+    }
+
+    /// Returns the envelope signatures collected so far.
+    #[inline]
+    pub fn envelope_signatures(&self) -> &[Signature] {
+        &self.envelope_signatures
+    }
+
+    /// Signs the party on behalf of an account: the envelope if the account is the payer,
+    /// otherwise the payload.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the account is neither the proposer, the payer nor an
+    /// authorizer of the transaction, if it would sign the payload after the envelope has been
+    /// signed, or if the signer could not sign.
+    pub async fn sign(&mut self, signer: &dyn PartySigner) -> Result<(), Error> {
+        let address = signer.address();
+
+        if address == self.payer() {
+            let signatures = signer
+                .sign_envelope(&self.party)
+                .await
+                .map_err(Error::Signing)?;
+            self.envelope_signatures.extend(signatures);
+        } else if self.party.signer_map.contains_key(address) {
+            if !self.envelope_signatures.is_empty() {
+                return Err(Error::EnvelopeSigned);
+            }
+
+            signer
+                .sign_payload(&mut self.party)
+                .await
+                .map_err(Error::Signing)?;
+        } else {
+            return Err(Error::UnknownSigner);
+        }
+
+        Ok(())
+    }
+
+    /// Adds the signatures of another copy of this party that are not collected yet.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the other party is for a different transaction, or if
+    /// either party has envelope signatures and the other has payload signatures it lacks, as the
+    /// envelope signatures would no longer cover every payload signature.
+    pub fn merge(&mut self, other: Self) -> Result<(), Error> {
+        if self.party.encode_payload() != other.party.encode_payload() {
+            return Err(Error::PayloadMismatch);
+        }
+
+        // Addresses are matched by signer index, as they might be padded differently.
+        let payload_signatures = other
+            .party
+            .payload_signatures
+            .iter()
+            .map(|signature| {
+                let signer_index = *other
+                    .party
+                    .signer_map
+                    .get(&signature.address)
+                    .ok_or(Error::UnknownSigner)?;
+                Ok(SignatureE {
+                    address: self.signer_address(signer_index).into(),
+                    ..signature.clone()
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let added = payload_signatures
+            .iter()
+            .any(|signature| !contains(&self.party.payload_signatures, signature));
+        let missing = self
+            .party
+            .payload_signatures
+            .iter()
+            .any(|signature| !contains(&payload_signatures, signature));
+
+        if (added && !self.envelope_signatures.is_empty())
+            || (missing && !other.envelope_signatures.is_empty())
+        {
+            return Err(Error::EnvelopeSigned);
+        }
+
+        for signature in payload_signatures {
+            if !contains(&self.party.payload_signatures, &signature) {
+                self.party.payload_signatures.push(signature);
+            }
+        }
+
+        for signature in other.envelope_signatures {
+            let signature = SignatureE {
+                address: self.payer().into(),
+                ..signature
+            };
+
+            if !contains(&self.envelope_signatures, &signature) {
+                self.envelope_signatures.push(signature);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Turns this into a transaction, ready to be sent.
+    pub fn into_transaction(self) -> PartyTransaction<Box<[u8]>, [u8; 64]> {
+        self.party.into_transaction(self.envelope_signatures)
+    }
+
+    /// Exports this party to JSON.
+    pub fn export_json(&self) -> String {
+        let party = &self.party;
+        let file = PartyFile {
+            version: VERSION,
+            script: party.script.to_string(),
+            arguments: party.arguments.iter().map(hex::encode).collect(),
+            reference_block: hex::encode(&party.reference_block),
+            gas_limit: party.gas_limit,
+            proposal_key: ProposalKeyFile {
+                address: encode_address(&party.proposer_address),
+                key_id: party.proposal_key_id,
+                sequence_number: party.proposal_key_sequence_number,
+            },
+            payer: encode_address(&party.payer),
+            authorizers: party.authorizers.iter().map(encode_address).collect(),
+            payload_signatures: party.payload_signatures.iter().map(Into::into).collect(),
+            envelope_signatures: self.envelope_signatures.iter().map(Into::into).collect(),
+        };
+
+        serde_json::to_string_pretty(&file).expect("parties can always be serialized")
+    }
+
+    /// Imports a party from JSON.
+    pub fn import_json(json: &str) -> Result<Self, Error> {
+        let file: PartyFile = serde_json::from_str(json)?;
+
+        if file.version != VERSION {
+            return Err(Error::UnsupportedVersion(file.version));
+        }
+
+        let arguments = file
+            .arguments
+            .iter()
+            .map(|argument| decode_hex(argument, "arguments"))
+            .collect::<Result<_, _>>()?;
+        let authorizers = file
+            .authorizers
+            .iter()
+            .map(|authorizer| decode_hex(authorizer, "authorizers"))
+            .collect::<Result<_, _>>()?;
+
+        let party = SigningParty::new(
+            file.script.into(),
+            arguments,
+            decode_hex(&file.reference_block, "reference_block")?,
+            file.gas_limit,
+            decode_hex(&file.proposal_key.address, "proposal_key.address")?,
+            file.proposal_key.key_id,
+            file.proposal_key.sequence_number,
+            decode_hex(&file.payer, "payer")?,
+            authorizers,
+        );
+
+        let payload_signatures = file
+            .payload_signatures
+            .iter()
+            .map(SignatureFile::decode)
+            .collect::<Result<_, _>>()?;
+        let envelope_signatures = file
+            .envelope_signatures
+            .iter()
+            .map(SignatureFile::decode)
+            .collect::<Result<_, _>>()?;
+
+        Self::with_signatures(party, payload_signatures, envelope_signatures)
+    }
+
+    /// Exports this party to the hex of the RLP encoding of the transaction, the format used by
+    /// `flow transactions build` and `flow transactions sign`.
+    pub fn export_rlp_hex(&self) -> String {
+        let party = &self.party;
+        let mut stream = RlpStream::new();
+        stream.begin_list(3);
+        party.encode_payload_to(&mut stream);
+        self.encode_signatures(&mut stream, &party.payload_signatures);
+        self.encode_signatures(&mut stream, &self.envelope_signatures);
+
+        hex::encode(stream.out())
+    }
+
+    /// Imports a party from the hex of the RLP encoding of the transaction.
+    ///
+    /// The addresses of the imported party are always 8 bytes long.
+    pub fn import_rlp_hex(rlp_hex: &str) -> Result<Self, Error> {
+        Self::decode_rlp(&decode_hex(rlp_hex.trim(), "transaction")?)
+    }
+
+    /// Decodes a party from the RLP encoding of a transaction, or of its envelope, which does not
+    /// include envelope signatures.
+    ///
+    /// The addresses of the decoded party are always 8 bytes long.
+    pub fn decode_rlp(bytes: &[u8]) -> Result<Self, Error> {
+        let transaction = Rlp::new(bytes);
+        let payload = transaction.at(0)?;
+
+        let script = String::from_utf8(payload.val_at::<Vec<u8>>(0)?)
+            .map_err(|_| Error::InvalidField("script"))?;
+        let arguments = payload
+            .at(1)?
+            .iter()
+            .map(|argument| argument.data().map(Into::into))
+            .collect::<Result<_, _>>()?;
+        let authorizers = payload
+            .at(8)?
+            .iter()
+            .map(|authorizer| authorizer.data().map(Into::into))
+            .collect::<Result<_, _>>()?;
+
+        let party = SigningParty::new(
+            script.into(),
+            arguments,
+            payload.at(2)?.data()?.into(),
+            payload.val_at(3)?,
+            payload.at(4)?.data()?.into(),
+            payload.val_at(5)?,
+            payload.val_at(6)?,
+            payload.at(7)?.data()?.into(),
+            authorizers,
+        );
+
+        let payload_signatures = decode_signatures(&party, &transaction.at(1)?)?;
+        let envelope_signatures = match transaction.item_count()? {
+            2 => Vec::new(),
+            _ => decode_signatures(&party, &transaction.at(2)?)?,
+        };
+
+        Self::with_signatures(party, payload_signatures, envelope_signatures)
+    }
+
+    pub(super) fn into_party(self) -> SigningParty {
+        self.party
+    }
+
+    fn payer(&self) -> &[u8] {
+        &self.party.payer
+    }
+
+    /// Returns the address of the signer with the index specified, the index must be valid.
+    fn signer_address(&self, signer_index: u32) -> &[u8] {
+        self.party
+            .signer_map
+            .iter()
+            .find(|(_, &index)| index == signer_index)
+            .map(|(address, _)| &**address)
+            .expect("signer indices of the same transaction match")
+    }
+
+    fn encode_signatures(&self, stream: &mut RlpStream, signatures: &[Signature]) {
+        stream.begin_list(signatures.len());
+        for signature in signatures {
+            stream
+                .begin_list(3)
+                .append(&self.party.signer_map[&signature.address])
+                .append(&signature.key_id)
+                .append(&signature.signature.as_ref());
+        }
+    }
+
+    /// Checks that the payload signatures are made by the proposer or the authorizers, and the
+    /// envelope signatures by the payer.
+    pub(super) fn with_signatures(
+        mut party: SigningParty,
+        payload_signatures: Vec<Signature>,
+        envelope_signatures: Vec<Signature>,
+    ) -> Result<Self, Error> {
+        for signature in &payload_signatures {
+            if signature.address != party.proposer_address
+                && !party.authorizers.contains(&signature.address)
+            {
+                return Err(Error::UnknownSigner);
+            }
+        }
+
+        if envelope_signatures
+            .iter()
+            .any(|signature| signature.address != party.payer)
+        {
+            return Err(Error::UnknownSigner);
+        }
+
+        party.payload_signatures = payload_signatures;
+
+        Ok(Self {
+            party,
+            envelope_signatures,
+        })
+    }
+}
+
+impl TryFrom<TransactionD> for PortableParty {
+    type Error = Error;
+
+    /// Converts a transaction decoded from the network, keeping its signatures.
+    fn try_from(transaction: TransactionD) -> Result<Self, Self::Error> {
+        let script = String::from_utf8(transaction.script.into_vec())
+            .map_err(|_| Error::InvalidField("script"))?;
+
+        let party = SigningParty::new(
+            script.into(),
+            transaction.arguments.into_inner().into(),
+            transaction.reference_block_id,
+            transaction.gas_limit,
+            transaction.proposal_key.address,
+            transaction.proposal_key.key_id.into(),
+            transaction.proposal_key.sequence_number,
+            transaction.payer,
+            transaction.authorizers.into_inner().into(),
+        );
+
+        let decode = |signatures: Repeated<Vec<SignatureD>>| {
+            signatures
+                .into_inner()
+                .into_iter()
+                .map(|signature| {
+                    Ok(SignatureE {
+                        address: signature.address,
+                        key_id: signature.key_id,
+                        signature: decode_signature(&signature.signature)?,
+                    })
+                })
+                .collect::<Result<_, Error>>()
+        };
+
+        Self::with_signatures(
+            party,
+            decode(transaction.payload_signatures)?,
+            decode(transaction.envelope_signatures)?,
+        )
+    }
+}
+
+impl SigningParty {
+    /// Decodes a party from the RLP encoding of a transaction, keeping its payload signatures.
+    ///
+    /// See [`PortableParty::decode_rlp`].
+    pub fn decode_rlp(bytes: &[u8]) -> Result<Self, Error> {
+        PortableParty::decode_rlp(bytes).map(PortableParty::into_party)
+    }
+}
+
+impl TryFrom<TransactionD> for SigningParty {
+    type Error = Error;
+
+    /// Converts a transaction decoded from the network, keeping its payload signatures so that
+    /// more accounts can sign it. Envelope signatures are dropped, as they have to be made again
+    /// after the payload signatures change.
+    fn try_from(transaction: TransactionD) -> Result<Self, Self::Error> {
+        PortableParty::try_from(transaction).map(PortableParty::into_party)
+    }
+}
+
+impl<H: FlowHasher> TryFrom<TransactionD> for PreHashedParty<H> {
+    type Error = Error;
+
+    /// Converts a transaction decoded from the network, keeping its payload signatures. See
+    /// [`SigningParty`]'s conversion.
+    fn try_from(transaction: TransactionD) -> Result<Self, Self::Error> {
+        SigningParty::try_from(transaction).map(SigningParty::into_prehashed)
+    }
+}
+
+fn contains(signatures: &[Signature], signature: &Signature) -> bool {
+    signatures
+        .iter()
+        .any(|sig| sig.address == signature.address && sig.key_id == signature.key_id)
+}
+
+fn encode_address(address: impl AsRef<[u8]>) -> String {
+    format!("0x{}", hex::encode(address))
+}
+
+fn decode_hex(s: &str, field: &'static str) -> Result<Box<[u8]>, Error> {
+    hex::decode(s.trim_start_matches("0x"))
+        .map(Into::into)
+        .map_err(|_| Error::InvalidField(field))
+}
+
+fn decode_signature(signature: &[u8]) -> Result<[u8; 64], Error> {
+    signature
+        .try_into()
+        .map_err(|_| Error::InvalidField("signature"))
+}
+
+fn decode_signatures(party: &SigningParty, signatures: &Rlp<'_>) -> Result<Vec<Signature>, Error> {
+    signatures
+        .iter()
+        .map(|signature| {
+            let signer_index: u32 = signature.val_at(0)?;
+            let address = party
+                .signer_map
+                .iter()
+                .find(|(_, &index)| index == signer_index)
+                .map(|(address, _)| address.clone())
+                .ok_or(Error::UnknownSigner)?;
+
+            Ok(SignatureE {
+                address,
+                key_id: signature.val_at(1)?,
+                signature: decode_signature(signature.at(2)?.data()?)?,
+            })
+        })
+        .collect()
+}
+
+#[derive(Serialize, Deserialize)]
+struct PartyFile {
+    version: u32,
+    script: String,
+    arguments: Vec<String>,
+    reference_block: String,
+    gas_limit: u64,
+    proposal_key: ProposalKeyFile,
+    payer: String,
+    authorizers: Vec<String>,
+    payload_signatures: Vec<SignatureFile>,
+    envelope_signatures: Vec<SignatureFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProposalKeyFile {
+    address: String,
+    key_id: u64,
+    sequence_number: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SignatureFile {
+    address: String,
+    key_id: u32,
+    signature: String,
+}
+
+impl SignatureFile {
+    fn decode(&self) -> Result<Signature, Error> {
+        Ok(SignatureE {
+            address: decode_hex(&self.address, "address")?,
+            key_id: self.key_id,
+            signature: decode_signature(&decode_hex(&self.signature, "signature")?)?,
+        })
+    }
+}
+
+impl From<&Signature> for SignatureFile {
+    fn from(signature: &Signature) -> Self {
+        Self {
+            address: encode_address(&signature.address),
+            key_id: signature.key_id,
+            signature: hex::encode(signature.signature),
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Collecting and verifying the signatures of a multi-party transaction.
+//!
+//! A [`SigningSession`] keeps track of who still has to sign a [`SigningParty`]. The proposer and
+//! the authorizers sign the payload first, then the payer signs the envelope. Every signature is
+//! verified against the keys of its account before it is accepted, and the transaction can only be
+//! taken out once every signer has signed with full weight.
+//!
+//! ```rust,no_run
+//! # use flow_sdk::client::TonicHyperFlowClient;
+//! # use flow_sdk::multi::{PartyBuilder, PartySigner};
+//! # use flow_sdk::multi::session::{Error, SigningSession};
+//! # async fn run(
+//! #     client: &mut TonicHyperFlowClient,
+//! #     proposer: &dyn PartySigner,
+//! #     payer: &dyn PartySigner,
+//! # ) -> Result<(), Error> {
+//! let party = PartyBuilder::new()
+//!     .script("transaction { prepare(signer: AuthAccount) {} }")
+//!     .reference_block([0; 32])
+//!     .proposer_address(proposer.address())
+//!     .proposal_key_id(0)
+//!     .proposal_key_sequence_number(42)
+//!     .payer(payer.address())
+//!     .authorizer(proposer.address())
+//!     .build();
+//!
+//! let mut session = SigningSession::new(client, party).await?;
+//! session.sign(proposer).await?;
+//! session.sign(payer).await?;
+//!
+//! let transaction = session.into_transaction()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use super::{PartySigner, PartyTransaction, SigningParty};
+use crate::access::{AccountResponse, GetAccountAtLatestBlockRequest};
+use crate::account::PADDED_TRANSACTION_DOMAIN_TAG;
+use crate::client::{FlowClient, GrpcClient};
+use crate::entities::{Account, AccountKey};
+use crate::message::FULL_WEIGHT;
+use crate::transaction::SignatureE;
+
+pub use crate::error::SigningSessionError as Error;
+
+type Signature = SignatureE<Box<[u8]>, [u8; 64]>;
+
+/// The role of an account in a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// The proposer signs the payload with the proposal key, or the envelope if it is also the
+    /// payer.
+    Proposer,
+
+    /// An authorizer signs the payload with full weight, or the envelope if it is also the payer.
+    Authorizer,
+
+    /// The payer signs the envelope with full weight.
+    Payer,
+}
+
+/// The state of a [`SigningSession`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionState {
+    /// The proposer or the authorizers have not signed the payload yet.
+    Payload,
+
+    /// The payload is signed, and the payer has not signed the envelope with full weight yet.
+    Envelope,
+
+    /// Every signer has signed, the transaction can be sent.
+    Complete,
+}
+
+/// Collects the signatures of a [`SigningParty`], verifying each of them.
+#[derive(Clone, Debug)]
+pub struct SigningSession {
+    party: SigningParty,
+    keys: HashMap<Box<[u8]>, Vec<AccountKey>>,
+    envelope_signatures: Vec<Signature>,
+}
+
+impl SigningSession {
+    /// Starts a session, querying the keys of the accounts taking part in the transaction from
+    /// the network.
+    ///
+    /// Signatures that the party already holds are verified as well.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the client returns any errors while making requests, or
+    /// if a signature of the party is rejected, see [`SigningSession::add_payload_signature`].
+    pub async fn new<Client>(
+        client: &mut FlowClient<Client>,
+        party: SigningParty,
+    ) -> Result<Self, Error>
+    where
+        Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    {
+        let mut accounts = Vec::new();
+
+        for address in party.signer_map.keys() {
+            let mut account = client
+                .account_at_latest_block(address)
+                .await
+                .map_err(Into::into)?;
+            account.address = address.clone();
+            accounts.push(account);
+        }
+
+        Self::with_accounts(party, accounts)
+    }
+
+    /// Starts a session with the accounts taking part in the transaction, for verifying
+    /// signatures offline.
+    ///
+    /// Signatures that the party already holds are verified as well.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the account of a signer is missing, or if a signature of
+    /// the party is rejected, see [`SigningSession::add_payload_signature`].
+    pub fn with_accounts(
+        mut party: SigningParty,
+        accounts: impl IntoIterator<Item = Account>,
+    ) -> Result<Self, Error> {
+        let mut keys: HashMap<_, _> = accounts
+            .into_iter()
+            .map(|account| (account.address, account.keys.into_inner()))
+            .collect();
+        keys.retain(|address, _| party.signer_map.contains_key(address));
+
+        if keys.len() != party.signer_map.len() {
+            return Err(Error::MissingAccount);
+        }
+
+        let signatures = std::mem::take(&mut party.payload_signatures);
+        let mut session = Self {
+            party,
+            keys,
+            envelope_signatures: Vec::new(),
+        };
+
+        for signature in signatures {
+            session.add_payload_signature(signature)?;
+        }
+
+        Ok(session)
+    }
+
+    /// Returns the party, with the payload signatures collected so far.
+    #[inline]
+    pub fn party(&self) -> &SigningParty {
+        &self.party
+    }
+
+    /// Returns the envelope signatures collected so far.
+    #[inline]
+    pub fn envelope_signatures(&self) -> &[Signature] {
+        &self.envelope_signatures
+    }
+
+    /// Returns the state of this session.
+    pub fn state(&self) -> SessionState {
+        if self.pending_payload_signers().next().is_some() {
+            SessionState::Payload
+        } else if self.is_signed(self.payer(), Role::Payer) {
+            SessionState::Complete
+        } else {
+            SessionState::Envelope
+        }
+    }
+
+    /// Returns every account that has to sign, with its roles.
+    ///
+    /// An account appears once for every role it has.
+    pub fn required_signers(&self) -> Vec<(&[u8], Role)> {
+        let party = &self.party;
+
+        std::iter::once((&*party.proposer_address, Role::Proposer))
+            .chain(
+                party
+                    .authorizers
+                    .iter()
+                    .map(|authorizer| (&**authorizer, Role::Authorizer)),
+            )
+            .chain(std::iter::once((&*party.payer, Role::Payer)))
+            .collect()
+    }
+
+    /// Returns the accounts that still have to sign, with their roles.
+    pub fn pending_signers(&self) -> Vec<(&[u8], Role)> {
+        self.required_signers()
+            .into_iter()
+            .filter(|&(address, role)| !self.is_signed(address, role))
+            .collect()
+    }
+
+    /// Adds a signature of the payload, made by the proposer or an authorizer.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    ///  - the envelope has already been signed
+    ///  - the account does not sign the payload, such as the payer or an account that does not
+    ///    take part in the transaction
+    ///  - the account already signed with the same key
+    ///  - the key could not be found on the account
+    ///  - the signature could not be verified with the key.
+    pub fn add_payload_signature(&mut self, signature: Signature) -> Result<(), Error> {
+        if !self.envelope_signatures.is_empty() {
+            return Err(Error::EnvelopeSigned);
+        }
+
+        let party = &self.party;
+        if signature.address == party.payer
+            || (signature.address != party.proposer_address
+                && !party.authorizers.contains(&signature.address))
+        {
+            return Err(Error::WrongRole);
+        }
+
+        let mut data = PADDED_TRANSACTION_DOMAIN_TAG.to_vec();
+        data.extend_from_slice(&party.encode_payload());
+        self.verify(&party.payload_signatures, &data, &signature)?;
+
+        self.party.payload_signatures.push(signature);
+
+        Ok(())
+    }
+
+    /// Adds a signature of the envelope, made by the payer.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    ///  - the proposer or the authorizers have not signed the payload yet
+    ///  - the account is not the payer
+    ///  - the account already signed with the same key
+    ///  - the key could not be found on the account
+    ///  - the signature could not be verified with the key.
+    pub fn add_envelope_signature(&mut self, signature: Signature) -> Result<(), Error> {
+        if self.pending_payload_signers().next().is_some() {
+            return Err(Error::PayloadIncomplete);
+        }
+
+        if signature.address != self.party.payer {
+            return Err(Error::WrongRole);
+        }
+
+        let mut data = PADDED_TRANSACTION_DOMAIN_TAG.to_vec();
+        data.extend_from_slice(&self.party.encode_envelope());
+        self.verify(&self.envelope_signatures, &data, &signature)?;
+
+        self.envelope_signatures.push(signature);
+
+        Ok(())
+    }
+
+    /// Signs on behalf of an account: the envelope if the account is the payer, otherwise the
+    /// payload.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the signer could not sign, or if a signature is
+    /// rejected, see [`SigningSession::add_payload_signature`] and
+    /// [`SigningSession::add_envelope_signature`].
+    pub async fn sign(&mut self, signer: &dyn PartySigner) -> Result<(), Error> {
+        if signer.address() == self.payer() {
+            for signature in signer
+                .sign_envelope(&self.party)
+                .await
+                .map_err(Error::Signing)?
+            {
+                self.add_envelope_signature(signature)?;
+            }
+        } else {
+            let mut party = self.party.clone();
+            signer
+                .sign_payload(&mut party)
+                .await
+                .map_err(Error::Signing)?;
+
+            let signed = self.party.payload_signatures.len();
+            for signature in party.payload_signatures.drain(signed..) {
+                self.add_payload_signature(signature)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Turns this into a transaction, ready to be sent.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the session is not [`SessionState::Complete`].
+    pub fn into_transaction(self) -> Result<PartyTransaction<Box<[u8]>, [u8; 64]>, Error> {
+        if self.state() != SessionState::Complete {
+            return Err(Error::Incomplete);
+        }
+
+        Ok(self.party.into_transaction(self.envelope_signatures))
+    }
+
+    fn payer(&self) -> &[u8] {
+        &self.party.payer
+    }
+
+    /// The proposer and the authorizers that have not signed the payload yet. Accounts that are
+    /// also the payer sign the envelope instead.
+    fn pending_payload_signers(&self) -> impl Iterator<Item = (&[u8], Role)> + '_ {
+        self.required_signers()
+            .into_iter()
+            .filter(move |&(address, role)| {
+                role != Role::Payer && address != self.payer() && !self.is_signed(address, role)
+            })
+    }
+
+    /// Whether an account has made the signatures that its role requires.
+    fn is_signed(&self, address: &[u8], role: Role) -> bool {
+        let signatures = if address == self.payer() {
+            &self.envelope_signatures
+        } else {
+            &self.party.payload_signatures
+        };
+        let mut signatures = signatures
+            .iter()
+            .filter(|signature| &*signature.address == address);
+
+        match role {
+            Role::Proposer => signatures
+                .any(|signature| u64::from(signature.key_id) == self.party.proposal_key_id),
+            Role::Authorizer | Role::Payer => {
+                let keys = &self.keys[address];
+                let weight: u32 = signatures
+                    .filter_map(|signature| find_key(keys, signature.key_id))
+                    .map(|key| key.weight)
+                    .sum();
+                weight >= FULL_WEIGHT
+            }
+        }
+    }
+
+    fn verify(
+        &self,
+        signed: &[Signature],
+        data: &[u8],
+        signature: &Signature,
+    ) -> Result<(), Error> {
+        let key_id = signature.key_id;
+
+        if signed
+            .iter()
+            .any(|sig| sig.address == signature.address && sig.key_id == key_id)
+        {
+            return Err(Error::DuplicateKey(key_id));
+        }
+
+        find_key(&self.keys[&signature.address], key_id)
+            .ok_or(Error::KeyNotFound(key_id))?
+            .verify(data, &signature.signature)
+            .map_err(|error| Error::InvalidSignature { key_id, error })
+    }
+}
+
+fn find_key(keys: &[AccountKey], key_id: u32) -> Option<&AccountKey> {
+    keys.iter().find(|key| key.index == key_id)
+}
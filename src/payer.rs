@@ -0,0 +1,329 @@
+//! Paying the fees of transactions built and signed by others.
+//!
+//! A [`PayerService`] receives parties whose payload has been signed by the proposer and the
+//! authorizers, verifies the signatures against the keys of their accounts, checks the parties
+//! against a [`PayerPolicy`], and signs their envelope as the payer. This lets an application pay
+//! the fees of its users' transactions.
+//!
+//! Parties are exchanged in the JSON format of [`PortableParty`]. The `flow-payer` binary, behind
+//! the `payer-server` feature, serves a [`PayerService`] over HTTP.
+//!
+//! ```rust,no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! use std::time::Duration;
+//!
+//! use flow_sdk::client::TonicHyperFlowClient;
+//! use flow_sdk::config::Config;
+//! use flow_sdk::payer::{PayerPolicy, PayerService};
+//!
+//! let config = Config::load()?;
+//! let payer = config.account("payer").await?;
+//! let mut client = payer.client_cloned();
+//! let policy = PayerPolicy::new()
+//!     .allow_script("transaction { prepare(signer: AuthAccount) {} }")
+//!     .max_gas_limit(1000)
+//!     .rate_limit(10, Duration::from_secs(60));
+//! let service = PayerService::new(payer, policy);
+//!
+//! # let request = String::new();
+//! // `request` holds a party exported with `PortableParty::export_json`.
+//! let response = service.sign_json(&mut client, &request).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use tiny_keccak::{Hasher, Sha3};
+
+use crate::access::{AccountResponse, GetAccountAtLatestBlockRequest};
+use crate::client::{FlowClient, GrpcClient};
+use crate::multi::interchange::PortableParty;
+use crate::multi::session::{Role, SigningSession};
+use crate::multi::{Party, PartySigner, SigningParty};
+
+pub use crate::error::PayerError as Error;
+
+/// Computes the SHA3-256 hash of a script, which identifies it in a [`PayerPolicy`].
+pub fn script_hash(script: impl AsRef<[u8]>) -> [u8; 32] {
+    let mut hasher = Sha3::v256();
+    hasher.update(script.as_ref());
+    let mut hash = [0; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// The transactions that a [`PayerService`] agrees to pay for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PayerPolicy {
+    allowed_scripts: Option<HashSet<[u8; 32]>>,
+    max_gas_limit: u64,
+    rate_limit: Option<(usize, Duration)>,
+}
+
+impl PayerPolicy {
+    /// Creates a policy that allows any script, with a maximum gas limit of 9999 and without rate
+    /// limits.
+    pub fn new() -> Self {
+        Self {
+            allowed_scripts: None,
+            max_gas_limit: 9999,
+            rate_limit: None,
+        }
+    }
+
+    /// Allows a script. Once a script is allowed, the scripts that are not allowed are rejected.
+    pub fn allow_script(self, script: impl AsRef<[u8]>) -> Self {
+        self.allow_script_hash(script_hash(script))
+    }
+
+    /// Allows a script by its hash, see [`script_hash`].
+    pub fn allow_script_hash(mut self, hash: [u8; 32]) -> Self {
+        self.allowed_scripts
+            .get_or_insert_with(HashSet::new)
+            .insert(hash);
+        self
+    }
+
+    /// Sets the maximum gas limit of transactions.
+    pub fn max_gas_limit(mut self, max_gas_limit: u64) -> Self {
+        self.max_gas_limit = max_gas_limit;
+        self
+    }
+
+    /// Limits how many transactions each account can have paid for within a period.
+    ///
+    /// A transaction counts towards the limit of its proposer and of each of its authorizers, so
+    /// that transactions without authorizers are limited too.
+    pub fn rate_limit(mut self, max_transactions: usize, period: Duration) -> Self {
+        self.rate_limit = Some((max_transactions, period));
+        self
+    }
+
+    /// Returns whether a script is allowed.
+    pub fn is_script_allowed(&self, script: impl AsRef<[u8]>) -> bool {
+        match &self.allowed_scripts {
+            Some(allowed) => allowed.contains(&script_hash(script)),
+            None => true,
+        }
+    }
+}
+
+impl Default for PayerPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Signs the envelope of transactions as the payer, if they comply with a [`PayerPolicy`].
+///
+/// The service can be shared between tasks.
+#[derive(Debug)]
+pub struct PayerService<Payer> {
+    payer: Payer,
+    policy: PayerPolicy,
+    // When the transactions paid for were signed, by proposer and authorizer.
+    history: Mutex<HashMap<Box<[u8]>, VecDeque<Instant>>>,
+}
+
+impl<Payer: PartySigner> PayerService<Payer> {
+    /// Creates a service that signs as the payer with the policy specified.
+    pub fn new(payer: Payer, policy: PayerPolicy) -> Self {
+        Self {
+            payer,
+            policy,
+            history: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the payer.
+    #[inline]
+    pub fn payer(&self) -> &Payer {
+        &self.payer
+    }
+
+    /// Returns the policy.
+    #[inline]
+    pub fn policy(&self) -> &PayerPolicy {
+        &self.policy
+    }
+
+    /// Checks a party against the policy without signing it.
+    ///
+    /// The payload signatures are verified against the keys of their accounts, queried from the
+    /// network.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    ///  - the payer of the transaction is not this payer
+    ///  - the proposer or an authorizer did not sign the payload, or signed it without enough
+    ///    weight
+    ///  - a payload signature could not be verified, or the client returns any errors while making
+    ///    requests
+    ///  - the script is not allowed, or the gas limit is too high
+    ///  - the proposer or an authorizer has reached the rate limit.
+    pub async fn check<Client>(
+        &self,
+        client: &mut FlowClient<Client>,
+        party: &SigningParty,
+    ) -> Result<(), Error>
+    where
+        Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    {
+        self.check_party(party)?;
+        self.verify_payload(client, party).await?;
+        self.check_rate_limit(party, Instant::now(), false)
+    }
+
+    /// Checks a party against the policy, and signs its envelope as the payer.
+    ///
+    /// # Errors
+    ///
+    /// See [`PayerService::check`].
+    pub async fn sign<Client>(
+        &self,
+        client: &mut FlowClient<Client>,
+        party: &mut PortableParty,
+    ) -> Result<(), Error>
+    where
+        Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    {
+        self.check_party(party.party())?;
+        self.verify_payload(client, party.party()).await?;
+        self.check_rate_limit(party.party(), Instant::now(), true)?;
+        party.sign(&self.payer).await?;
+        Ok(())
+    }
+
+    /// Signs a party exported with [`PortableParty::export_json`], returning it with the envelope
+    /// signatures of the payer.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the party could not be imported, or if it does not
+    /// comply with the policy, see [`PayerService::check`].
+    pub async fn sign_json<Client>(
+        &self,
+        client: &mut FlowClient<Client>,
+        json: &str,
+    ) -> Result<String, Error>
+    where
+        Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    {
+        let mut party = PortableParty::import_json(json)?;
+        self.sign(client, &mut party).await?;
+        Ok(party.export_json())
+    }
+
+    /// Verifies the payload signatures against the keys of their accounts, so that the rate
+    /// limit only counts accounts that signed with full weight.
+    async fn verify_payload<Client>(
+        &self,
+        client: &mut FlowClient<Client>,
+        party: &SigningParty,
+    ) -> Result<(), Error>
+    where
+        Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    {
+        let session = SigningSession::new(client, party.clone()).await?;
+        let payer = self.payer.address();
+        let unsigned = session
+            .pending_signers()
+            .into_iter()
+            .find(|&(address, role)| role != Role::Payer && address != payer);
+
+        match unsigned {
+            Some((signer, _)) => Err(Error::MissingSignature(signer.into())),
+            None => Ok(()),
+        }
+    }
+
+    fn check_party(&self, party: &SigningParty) -> Result<(), Error> {
+        let payer = self.payer.address();
+        let script = Party::<Sha3>::script(party);
+        let gas_limit = Party::<Sha3>::gas_limit(party);
+
+        if Party::<Sha3>::payer(party) != payer {
+            return Err(Error::WrongPayer);
+        }
+
+        let payload_signers = std::iter::once(Party::<Sha3>::proposer_address(party))
+            .chain(Party::<Sha3>::authorizers(party).iter().map(|a| &**a))
+            .filter(|&signer| signer != payer);
+
+        for signer in payload_signers {
+            if !party
+                .payload_signatures()
+                .iter()
+                .any(|signature| &*signature.address == signer)
+            {
+                return Err(Error::MissingSignature(signer.into()));
+            }
+        }
+
+        if !self.policy.is_script_allowed(script) {
+            return Err(Error::ScriptNotAllowed(script_hash(script)));
+        }
+
+        if gas_limit > self.policy.max_gas_limit {
+            return Err(Error::GasLimitTooHigh {
+                gas_limit,
+                max: self.policy.max_gas_limit,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks that neither the proposer nor any authorizer has reached the rate limit, recording
+    /// the transaction if it is to be signed.
+    fn check_rate_limit(
+        &self,
+        party: &SigningParty,
+        now: Instant,
+        record: bool,
+    ) -> Result<(), Error> {
+        let (max_transactions, period) = match self.policy.rate_limit {
+            Some(rate_limit) => rate_limit,
+            None => return Ok(()),
+        };
+
+        // An account proposing or authorizing more than once pays for a single transaction.
+        let accounts: HashSet<&[u8]> = std::iter::once(Party::<Sha3>::proposer_address(party))
+            .chain(Party::<Sha3>::authorizers(party).iter().map(|a| &**a))
+            .collect();
+        let mut history = self.history.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // Forget the transactions outside of the period, and the authorizers without any.
+        history.retain(|_, signed| {
+            while signed
+                .front()
+                .is_some_and(|&time| now.duration_since(time) >= period)
+            {
+                signed.pop_front();
+            }
+            !signed.is_empty()
+        });
+
+        for &account in &accounts {
+            let signed = history.get(account).map_or(0, VecDeque::len);
+
+            if signed >= max_transactions {
+                return Err(Error::RateLimited(account.into()));
+            }
+        }
+
+        if record {
+            for account in accounts {
+                history.entry(account.into()).or_default().push_back(now);
+            }
+        }
+
+        Ok(())
+    }
+}
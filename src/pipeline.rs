@@ -0,0 +1,387 @@
+//! Sending many transactions concurrently, following each of them until it is finalized.
+//!
+//! A [`Pipeline`] signs and submits the transactions of a stream one at a time, and follows the
+//! ones in flight concurrently, up to a bounded number. With a [`ProposerPool`], every transaction
+//! is proposed with a free key of the pool. Otherwise, the primary key of the account proposes
+//! every transaction, its sequence number being tracked locally.
+//!
+//! The outcomes are yielded in the order the transactions are finalized.
+//!
+//! ```rust,no_run
+//! # use flow_sdk::prelude::*;
+//! # use flow_sdk::pipeline::Pipeline;
+//! # use flow_sdk::proposer::ProposerPool;
+//! # use flow_sdk::transaction::TransactionHeader;
+//! # use futures_util::StreamExt;
+//! # async fn run(
+//! #     mut account: Account<TonicHyperFlowClient>,
+//! #     headers: Vec<TransactionHeader<Vec<Box<[u8]>>>>,
+//! # ) {
+//! // The account must be logged in with the keys of the pool.
+//! let pool = ProposerPool::new(account.address(), 0..8);
+//!
+//! let mut outcomes = Pipeline::new(&mut account)
+//!     .proposer_pool(&pool)
+//!     .concurrency(8)
+//!     .submit(futures_util::stream::iter(headers));
+//!
+//! while let Some(outcome) = outcomes.next().await {
+//!     match outcome.result {
+//!         Ok(result) => println!("#{} sealed with {} events", outcome.index, result.events.len()),
+//!         Err(e) => println!("#{} failed: {}", outcome.index, e),
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! [`ProposerPool`]: crate::proposer::ProposerPool
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures_util::stream::FuturesUnordered;
+use futures_util::{Stream, StreamExt};
+
+use crate::access::{
+    AccountResponse, BlockHeaderResponse, GetAccountAtLatestBlockRequest,
+    GetLatestBlockHeaderRequest, GetTransactionRequest, SendTransactionRequest,
+    SendTransactionResponse, TransactionResultResponse,
+};
+use crate::account::{Account, SendOptions};
+use crate::algorithms::{AsyncFlowSigner, FlowHasher, Signature};
+use crate::cadence_error::is_proposal_key_error;
+use crate::client::{FlowClient, GrpcClient};
+use crate::error::TransactionStatusError;
+use crate::proposer::{ProposalKey, ProposerPool};
+use crate::transaction::{SignatureE, StatusStream, TransactionHeader, TransactionStatus};
+
+pub use crate::error::PipelineError as Error;
+
+type Tracking<'a> = Pin<Box<dyn Future<Output = Outcome> + 'a>>;
+
+/// The outcome of a transaction sent by a [`Pipeline`].
+#[derive(Debug)]
+pub struct Outcome {
+    /// The position of the transaction in the stream of transactions submitted.
+    pub index: usize,
+
+    /// The ID of the transaction, if it was sent.
+    pub id: Option<Vec<u8>>,
+
+    /// The result of the sealed transaction, containing its status and events.
+    pub result: Result<TransactionResultResponse, Error>,
+}
+
+/// Sends the transactions of a stream with bounded concurrency.
+///
+/// The account is the proposer, the payer and the first authorizer of every transaction, unless
+/// the [`SendOptions`] specify other authorizers or another payer.
+pub struct Pipeline<'a, Client, SecretKey, Signer, Hasher> {
+    account: &'a mut Account<Client, SecretKey, Signer, Hasher>,
+    pool: Option<&'a ProposerPool>,
+    send_options: SendOptions<'a>,
+    concurrency: usize,
+    delay: Duration,
+    timeout: Duration,
+}
+
+impl<'a, Client, SecretKey, Signer, Hasher> Pipeline<'a, Client, SecretKey, Signer, Hasher>
+where
+    Client: Clone + 'a,
+    Signer: AsyncFlowSigner<SecretKey = SecretKey>,
+    Hasher: FlowHasher,
+{
+    /// Creates a pipeline sending from an account, with 16 transactions in flight at most.
+    ///
+    /// Transactions are followed every 2 seconds, for 60 seconds at most.
+    pub fn new(account: &'a mut Account<Client, SecretKey, Signer, Hasher>) -> Self {
+        Self {
+            account,
+            pool: None,
+            send_options: SendOptions::new(),
+            concurrency: 16,
+            delay: Duration::from_secs(2),
+            timeout: Duration::from_secs(60),
+        }
+    }
+
+    /// Proposes every transaction with a key of the pool, instead of the primary key.
+    ///
+    /// No more transactions are in flight than there are keys in the pool.
+    pub fn proposer_pool(mut self, pool: &'a ProposerPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Sets the options every transaction is sent with, such as the gas limit.
+    ///
+    /// The reference block, the proposal key and the sequence number are set for each
+    /// transaction.
+    pub fn send_options(mut self, options: SendOptions<'a>) -> Self {
+        self.send_options = options;
+        self
+    }
+
+    /// Sets how many transactions can be in flight at the same time.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the delay between queries about the status of each transaction.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Sets how long to wait for each transaction to be sealed.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Submits the transactions of a stream, yielding the outcome of each transaction once it is
+    /// finalized.
+    ///
+    /// Without a proposer pool, this enables tracking the sequence number of the primary key, see
+    /// [`Account::track_sequence_number`]. When a transaction expires or its sequence number does
+    /// not match, no more transactions are sent until the ones in flight are finalized, then the
+    /// sequence number is resynced.
+    ///
+    /// With a proposer pool, transactions wait for the keys of the pool to be released by the
+    /// transactions in flight. If no key can be acquired while none are in flight, because the
+    /// keys are used outside of the pipeline or could not be resynced, the transaction fails and
+    /// no more transactions are sent. The remaining transactions are left in the stream, which
+    /// can be passed by mutable reference to submit them later.
+    pub fn submit<Transactions, Arguments, Argument>(
+        self,
+        transactions: Transactions,
+    ) -> impl Stream<Item = Outcome> + Unpin + 'a
+    where
+        Transactions: Stream<Item = TransactionHeader<Arguments>> + 'a,
+        Client: for<'b> GrpcClient<GetAccountAtLatestBlockRequest<&'b [u8]>, AccountResponse>,
+        Client: GrpcClient<GetLatestBlockHeaderRequest, BlockHeaderResponse>,
+        Client: for<'b> GrpcClient<GetTransactionRequest<&'b [u8]>, TransactionResultResponse>,
+        Client: GrpcClient<
+            SendTransactionRequest<
+                Box<[u8]>,
+                Vec<Box<[u8]>>,
+                Box<[u8]>,
+                Box<[u8]>,
+                Box<[u8]>,
+                Vec<Box<[u8]>>,
+                Vec<SignatureE<Box<[u8]>, [u8; 64]>>,
+                Vec<SignatureE<Box<[u8]>, [u8; 64]>>,
+            >,
+            SendTransactionResponse,
+        >,
+        Signer::Signature: Signature<Serialized = [u8; 64]>,
+        Arguments: AsRef<[Argument]> + 'a,
+        Argument: AsRef<[u8]> + 'a,
+    {
+        if self.pool.is_none() && self.account.tracked_sequence_number().is_none() {
+            self.account.track_sequence_number(true);
+        }
+
+        let state = State {
+            pipeline: self,
+            transactions: Box::pin(transactions),
+            pending: None,
+            tracking: FuturesUnordered::new(),
+            next_index: 0,
+            stopped: false,
+            resync: false,
+        };
+
+        Box::pin(futures_util::stream::unfold(
+            state,
+            |mut state| async move {
+                let outcome = state.next().await?;
+                Some((outcome, state))
+            },
+        ))
+    }
+}
+
+struct State<'a, Client, SecretKey, Signer, Hasher, Transactions: Stream> {
+    pipeline: Pipeline<'a, Client, SecretKey, Signer, Hasher>,
+    transactions: Pin<Box<Transactions>>,
+    // A transaction pulled from the stream, waiting for a key to be released.
+    pending: Option<(usize, Transactions::Item)>,
+    tracking: FuturesUnordered<Tracking<'a>>,
+    next_index: usize,
+    // No more transactions are pulled from the stream.
+    stopped: bool,
+    // The sequence number of the primary key is resynced once nothing is in flight.
+    resync: bool,
+}
+
+impl<'a, Client, SecretKey, Signer, Hasher, Transactions, Arguments>
+    State<'a, Client, SecretKey, Signer, Hasher, Transactions>
+where
+    Transactions: Stream<Item = TransactionHeader<Arguments>>,
+    Client: Clone + 'a,
+    Client: for<'b> GrpcClient<GetAccountAtLatestBlockRequest<&'b [u8]>, AccountResponse>,
+    Client: GrpcClient<GetLatestBlockHeaderRequest, BlockHeaderResponse>,
+    Client: for<'b> GrpcClient<GetTransactionRequest<&'b [u8]>, TransactionResultResponse>,
+    Client: GrpcClient<
+        SendTransactionRequest<
+            Box<[u8]>,
+            Vec<Box<[u8]>>,
+            Box<[u8]>,
+            Box<[u8]>,
+            Box<[u8]>,
+            Vec<Box<[u8]>>,
+            Vec<SignatureE<Box<[u8]>, [u8; 64]>>,
+            Vec<SignatureE<Box<[u8]>, [u8; 64]>>,
+        >,
+        SendTransactionResponse,
+    >,
+    Signer: AsyncFlowSigner<SecretKey = SecretKey>,
+    Signer::Signature: Signature<Serialized = [u8; 64]>,
+    Hasher: FlowHasher,
+{
+    /// Sends transactions until the pipeline is full, then waits for the next transaction to be
+    /// finalized.
+    async fn next<Argument>(&mut self) -> Option<Outcome>
+    where
+        Arguments: AsRef<[Argument]>,
+        Argument: AsRef<[u8]>,
+    {
+        if self.resync && self.tracking.is_empty() {
+            self.pipeline.account.resync_sequence_number();
+            self.resync = false;
+        }
+
+        while !self.stopped && !self.resync && self.tracking.len() < self.pipeline.concurrency {
+            let (index, transaction) = match self.pending.take() {
+                Some(pending) => pending,
+                None => match self.transactions.next().await {
+                    Some(transaction) => {
+                        self.next_index += 1;
+                        (self.next_index - 1, transaction)
+                    }
+                    None => {
+                        self.stopped = true;
+                        break;
+                    }
+                },
+            };
+
+            // The transaction is pulled before acquiring a key, so that no key is held while
+            // waiting for the stream.
+            let key = match self.pipeline.pool {
+                Some(pool) => match pool.acquire(self.pipeline.account.client()).await {
+                    Ok(key @ Some(_)) => key,
+                    // Every key is in flight, wait for one to be released.
+                    Ok(None) if !self.tracking.is_empty() => {
+                        self.pending = Some((index, transaction));
+                        break;
+                    }
+                    // No key would be released, the remaining transactions are left in the stream.
+                    result => {
+                        self.stopped = true;
+                        return Some(Outcome {
+                            index,
+                            id: None,
+                            result: Err(result.err().map_or(Error::NoFreeKey, Into::into)),
+                        });
+                    }
+                },
+                None => None,
+            };
+
+            let mut options = self.pipeline.send_options.clone();
+            if let Some(key) = &key {
+                options = options.proposal_key(key);
+            }
+
+            // Dropping the key on errors resyncs it.
+            let id = match self
+                .pipeline
+                .account
+                .send_transaction_header_with(&transaction, options)
+                .await
+            {
+                Ok(response) => response.id,
+                Err(e) => {
+                    return Some(Outcome {
+                        index,
+                        id: None,
+                        result: Err(e.into()),
+                    })
+                }
+            };
+
+            self.tracking.push(track(
+                index,
+                id,
+                key,
+                self.pipeline.account.client_cloned(),
+                self.pipeline.delay,
+                self.pipeline.timeout,
+            ));
+        }
+
+        let outcome = self.tracking.next().await?;
+
+        // The sequence number of the primary key is out of sync. The transactions in flight were
+        // proposed with the following sequence numbers, so it is resynced once they are finalized.
+        if self.pipeline.pool.is_none() {
+            match &outcome.result {
+                Err(Error::Status(TransactionStatusError::Failed { error_message, .. }))
+                    if !is_proposal_key_error(error_message) => {}
+                Err(_) => self.resync = true,
+                Ok(_) => {}
+            }
+        }
+
+        Some(outcome)
+    }
+}
+
+/// Follows a transaction until it is sealed, then returns its proposal key to the pool.
+fn track<'a, Client>(
+    index: usize,
+    id: Vec<u8>,
+    key: Option<ProposalKey<'a>>,
+    client: FlowClient<Client>,
+    delay: Duration,
+    timeout: Duration,
+) -> Tracking<'a>
+where
+    Client: for<'b> GrpcClient<GetTransactionRequest<&'b [u8]>, TransactionResultResponse> + 'a,
+{
+    Box::pin(async move {
+        let mut stream = StatusStream::new(&id, client).delay(delay).timeout(timeout);
+
+        let mut error = None;
+        while let Some(status) = stream.next().await {
+            if let Err(e) = status {
+                error = Some(e);
+                break;
+            }
+        }
+        let last_response = stream.into_last_response();
+
+        if let (Some(key), Some(response)) = (key, &last_response) {
+            key.release(response);
+        }
+
+        let result = match (error, last_response) {
+            (Some(e), _) => Err(e.into()),
+            (None, Some(response)) if response.status == TransactionStatus::Expired => {
+                Err(TransactionStatusError::Expired.into())
+            }
+            (None, Some(response)) => Ok(response),
+            (None, None) => Err(TransactionStatusError::Timeout.into()),
+        };
+
+        Outcome {
+            index,
+            id: Some(id),
+            result,
+        }
+    })
+}
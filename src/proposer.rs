@@ -0,0 +1,280 @@
+//! Pools of proposal keys, for sending many transactions from one account concurrently.
+//!
+//! Flow processes the transactions proposed by a key in the order of their sequence numbers, so
+//! one key can only have one transaction in flight. A [`ProposerPool`] tracks the sequence numbers
+//! of many keys of one account locally, and hands out a free key for each transaction.
+//!
+//! ```rust,no_run
+//! # use flow_sdk::prelude::*;
+//! # use flow_sdk::account::SendOptions;
+//! # use flow_sdk::proposer::ProposerPool;
+//! # use flow_sdk::transaction::TransactionHeader;
+//! # async fn run(
+//! #     mut account: Account<TonicHyperFlowClient>,
+//! #     header: TransactionHeader<Vec<Box<[u8]>>>,
+//! # ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! // The account must be logged in with the keys of the pool.
+//! let pool = ProposerPool::new(account.address(), 0..4);
+//!
+//! let key = pool.acquire(account.client()).await?.expect("all keys are in use");
+//! let options = SendOptions::new().proposal_key(&key);
+//! let response = account.send_transaction_header_with(&header, options).await?;
+//!
+//! // Returns the key to the pool, with its sequence number incremented if it was used.
+//! key.finalize(response.finalize(account.client())).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::mem;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::access::{AccountResponse, GetAccountAtLatestBlockRequest, TransactionResultResponse};
+use crate::cadence_error::is_proposal_key_error;
+use crate::client::{FlowClient, GrpcClient};
+use crate::transaction::TransactionStatus;
+
+pub use crate::error::ProposerPoolError as Error;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum KeyState {
+    /// The key can be handed out.
+    Free,
+
+    /// The key has been handed out.
+    InUse,
+
+    /// The sequence number of the key is not known, it must be queried from the network.
+    Stale,
+}
+
+#[derive(Debug)]
+struct PoolKey {
+    key_id: u32,
+    sequence_number: u64,
+    state: KeyState,
+}
+
+/// A pool of proposal keys of one account.
+///
+/// The pool can be shared between tasks, each task acquires its own key.
+#[derive(Debug)]
+pub struct ProposerPool {
+    address: Box<[u8]>,
+    keys: Mutex<Vec<PoolKey>>,
+}
+
+impl ProposerPool {
+    /// Creates a pool with the keys of an account, specified by their indices.
+    ///
+    /// The sequence numbers are queried from the network when keys are first acquired.
+    pub fn new(address: impl Into<Box<[u8]>>, key_ids: impl IntoIterator<Item = u32>) -> Self {
+        Self {
+            address: address.into(),
+            keys: Mutex::new(
+                key_ids
+                    .into_iter()
+                    .map(|key_id| PoolKey {
+                        key_id,
+                        sequence_number: 0,
+                        state: KeyState::Stale,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the address of the account.
+    #[inline]
+    pub fn address(&self) -> &[u8] {
+        &self.address
+    }
+
+    /// Returns how many keys can be handed out without querying the network.
+    pub fn available(&self) -> usize {
+        self.lock()
+            .iter()
+            .filter(|key| key.state == KeyState::Free)
+            .count()
+    }
+
+    /// Hands out a free key, returns `None` if every key is in use or has to be resynced.
+    pub fn try_acquire(&self) -> Option<ProposalKey<'_>> {
+        let mut keys = self.lock();
+        let key = keys.iter_mut().find(|key| key.state == KeyState::Free)?;
+        key.state = KeyState::InUse;
+
+        Some(ProposalKey {
+            pool: self,
+            key_id: key.key_id,
+            sequence_number: key.sequence_number,
+        })
+    }
+
+    /// Hands out a free key, resyncing the keys whose sequence numbers are out of sync if there
+    /// are no free keys.
+    ///
+    /// Returns `None` if every key is in use.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the keys could not be resynced, see
+    /// [`ProposerPool::resync`].
+    pub async fn acquire<Client>(
+        &self,
+        client: &mut FlowClient<Client>,
+    ) -> Result<Option<ProposalKey<'_>>, Error>
+    where
+        Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    {
+        if let Some(key) = self.try_acquire() {
+            return Ok(Some(key));
+        }
+
+        if self.lock().iter().any(|key| key.state == KeyState::Stale) {
+            self.resync(client).await?;
+        }
+
+        Ok(self.try_acquire())
+    }
+
+    /// Queries the sequence numbers of the keys that are out of sync from the network, so that
+    /// they can be handed out again.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    ///  - the client returns any errors while making requests
+    ///  - a key could not be found on the account, or is revoked.
+    pub async fn resync<Client>(&self, client: &mut FlowClient<Client>) -> Result<(), Error>
+    where
+        Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    {
+        let account = client
+            .account_at_latest_block(&*self.address)
+            .await
+            .map_err(Into::into)?;
+
+        let mut keys = self.lock();
+
+        for key in keys.iter_mut().filter(|key| key.state == KeyState::Stale) {
+            let account_key = account
+                .keys
+                .iter()
+                .find(|account_key| account_key.index == key.key_id)
+                .ok_or(Error::KeyNotFound(key.key_id))?;
+
+            if account_key.revoked {
+                return Err(Error::KeyRevoked(key.key_id));
+            }
+
+            key.sequence_number = account_key.sequence_number.into();
+            key.state = KeyState::Free;
+        }
+
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<PoolKey>> {
+        // The keys are always left in a consistent state.
+        self.keys.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn put_back(&self, key_id: u32, sequence_number: u64, state: KeyState) {
+        let mut keys = self.lock();
+        if let Some(key) = keys.iter_mut().find(|key| key.key_id == key_id) {
+            key.sequence_number = sequence_number;
+            key.state = state;
+        }
+    }
+}
+
+/// A proposal key handed out by a [`ProposerPool`].
+///
+/// The key must be returned to the pool after its transaction is finalized, with
+/// [`ProposalKey::finalize`] or [`ProposalKey::release`]. If this is dropped instead, the key is
+/// resynced before it is handed out again.
+#[derive(Debug)]
+#[must_use = "the key should be released after its transaction is finalized"]
+pub struct ProposalKey<'a> {
+    pool: &'a ProposerPool,
+    key_id: u32,
+    sequence_number: u64,
+}
+
+impl ProposalKey<'_> {
+    /// Returns the address of the account.
+    #[inline]
+    pub fn address(&self) -> &[u8] {
+        self.pool.address()
+    }
+
+    /// Returns the index of the key.
+    #[inline]
+    pub fn key_id(&self) -> u32 {
+        self.key_id
+    }
+
+    /// Returns the sequence number to propose the transaction with.
+    #[inline]
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    /// Waits for the transaction to be finalized, such as with [`Finalize`], then returns the key
+    /// to the pool.
+    ///
+    /// If the transaction was not finalized in time or an error occured, the key is resynced
+    /// before it is handed out again.
+    ///
+    /// [`Finalize`]: crate::transaction::Finalize
+    pub async fn finalize<Error>(
+        self,
+        finalize: impl Future<Output = Result<Option<TransactionResultResponse>, Error>>,
+    ) -> Result<Option<TransactionResultResponse>, Error> {
+        let result = finalize.await;
+
+        if let Ok(Some(response)) = &result {
+            self.release(response);
+        }
+
+        result
+    }
+
+    /// Returns the key to the pool after its transaction is finalized.
+    ///
+    /// The sequence number is incremented if the transaction was sealed, even if it failed. The
+    /// key is resynced if the transaction failed because of its proposal key, such as when the
+    /// sequence number did not match.
+    pub fn release(self, result: &TransactionResultResponse) {
+        match result.status {
+            TransactionStatus::Sealed if !is_proposal_key_error(&result.error_message) => {
+                self.put_back(1, KeyState::Free)
+            }
+            TransactionStatus::Expired => self.put_back(0, KeyState::Free),
+            _ => self.put_back(0, KeyState::Stale),
+        }
+    }
+
+    /// Returns the key to the pool when its transaction was not sent, so the sequence number is
+    /// unchanged.
+    pub fn release_unused(self) {
+        self.put_back(0, KeyState::Free)
+    }
+
+    /// Returns the key to the pool, incrementing its sequence number by the amount specified.
+    fn put_back(self, increment: u64, state: KeyState) {
+        self.pool
+            .put_back(self.key_id, self.sequence_number + increment, state);
+        mem::forget(self);
+    }
+}
+
+impl Drop for ProposalKey<'_> {
+    fn drop(&mut self) {
+        self.pool
+            .put_back(self.key_id, self.sequence_number, KeyState::Stale);
+    }
+}
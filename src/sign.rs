@@ -3,7 +3,7 @@
 use std::iter::{FusedIterator, Map, Zip};
 use std::slice;
 
-use crate::algorithms::{FlowSigner, Signature};
+use crate::algorithms::{AsyncFlowSigner, Digest, FlowSigner, Signature};
 use crate::transaction::SignatureE;
 
 /// Specification of multisign. Has multiple keys and specifies which one to use when proposing.
@@ -121,23 +121,26 @@ where
 }
 
 /// A signature iterator. Iterates over the secret keys and sign the data.
+///
+/// This is an [`Iterator`] for [`FlowSigner`]s. Signers that sign asynchronously can use
+/// [`SignIter::next_async`] and [`SignIter::collect_async`] instead.
 #[derive(Clone)]
-pub struct SignIter<'a, Signer: FlowSigner> {
-    data: [u8; 32],
+pub struct SignIter<'a, Signer: AsyncFlowSigner> {
+    data: Digest,
     signer: &'a Signer,
     idx: usize,
     method: &'a SignMethod<Signer::SecretKey>,
 }
 
-impl<'a, Signer: FlowSigner> SignIter<'a, Signer> {
+impl<'a, Signer: AsyncFlowSigner> SignIter<'a, Signer> {
     /// Creates a new signature iterator with the data being signed, the signer, and the method.
     pub fn new(
-        data: [u8; 32],
+        data: impl Into<Digest>,
         signer: &'a Signer,
         method: &'a SignMethod<Signer::SecretKey>,
     ) -> Self {
         Self {
-            data,
+            data: data.into(),
             signer,
             idx: 0,
             method,
@@ -160,7 +163,31 @@ impl<'a, Signer: FlowSigner> SignIter<'a, Signer> {
         self.remaining() > 0
     }
 
-    unsafe fn next_unchecked(&mut self) -> Signer::Signature {
+    /// Signs with the next secret key asynchronously, returns `None` if there are no signatures
+    /// left to sign.
+    pub async fn next_async(&mut self) -> Option<Result<Signer::Signature, Signer::Error>> {
+        if !self.has_remaining() {
+            return None;
+        }
+
+        // SAFETY: checked that this iterator has remaining items
+        let secret_key = unsafe { self.next_key_unchecked() };
+
+        Some(self.signer.sign_digest_async(self.data, secret_key).await)
+    }
+
+    /// Signs with all the remaining secret keys asynchronously, one after another.
+    pub async fn collect_async(mut self) -> Result<Vec<Signer::Signature>, Signer::Error> {
+        let mut signatures = Vec::with_capacity(self.remaining());
+
+        while let Some(signature) = self.next_async().await {
+            signatures.push(signature?);
+        }
+
+        Ok(signatures)
+    }
+
+    unsafe fn next_key_unchecked(&mut self) -> &'a Signer::SecretKey {
         let secret_key = match self.method {
             SignMethod::One(sec) => &sec.key,
             SignMethod::Multi(multi) => &multi.keys.get_unchecked(self.idx).key,
@@ -168,7 +195,15 @@ impl<'a, Signer: FlowSigner> SignIter<'a, Signer> {
 
         self.idx += 1;
 
-        self.signer.sign_populated(self.data, secret_key)
+        secret_key
+    }
+}
+
+impl<Signer: FlowSigner> SignIter<'_, Signer> {
+    unsafe fn next_unchecked(&mut self) -> Signer::Signature {
+        let secret_key = self.next_key_unchecked();
+
+        self.signer.sign_digest(self.data, secret_key)
     }
 }
 
@@ -0,0 +1,127 @@
+use crate::algorithms::*;
+
+// Test vector from RFC 6979, A.2.5 (P-256, SHA-256, message "sample").
+const P256_SK: &str = "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+const P256_PK: &str = "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6\
+                       7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299";
+const SAMPLE_SHA256: &str = "af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf";
+const SAMPLE_SIG: &str = "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716\
+                          f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8";
+
+#[test]
+fn sha2_hashing() {
+    let mut hasher = <sha2::Sha256 as FlowHasher>::new();
+    hasher.update("sam");
+    hasher.update("ple");
+
+    assert_eq!(hex::encode(FlowHasher::finalize(hasher)), SAMPLE_SHA256);
+    assert_eq!(<sha2::Sha256 as FlowHasher>::Algorithm::CODE, 1);
+    assert_eq!(Sha2_384::CODE, 2);
+    assert_eq!(Sha3_384::CODE, 4);
+}
+
+#[test]
+fn p256_signing() {
+    let signer = P256Signer::new();
+    let secret_key = p256::ecdsa::SigningKey::from_slice(&hex::decode(P256_SK).unwrap()).unwrap();
+
+    let public_key = signer.to_public_key(&secret_key);
+    assert_eq!(
+        hex::encode(signer.serialize_public_key(&public_key)),
+        P256_PK
+    );
+
+    let mut hashed = [0; 32];
+    hashed.copy_from_slice(&hex::decode(SAMPLE_SHA256).unwrap());
+
+    let signature = signer.sign_populated(hashed, &secret_key);
+    assert_eq!(hex::encode(signature.serialize()), SAMPLE_SIG);
+}
+
+#[test]
+fn p256_verification() {
+    let verifier = P256Verifier::new();
+
+    let mut public_key = [0; 64];
+    public_key.copy_from_slice(&hex::decode(P256_PK).unwrap());
+    let mut hashed = [0; 32];
+    hashed.copy_from_slice(&hex::decode(SAMPLE_SHA256).unwrap());
+    let mut signature = hex::decode(SAMPLE_SIG).unwrap();
+
+    assert_eq!(
+        verifier.verify_populated(hashed, &signature, &public_key),
+        Ok(())
+    );
+
+    signature[0] ^= 1;
+    assert_eq!(
+        verifier.verify_populated(hashed, &signature, &public_key),
+        Err(crate::error::VerifyError::InvalidSignature)
+    );
+    assert_eq!(
+        verifier.verify_populated(hashed, &signature[1..], &public_key),
+        Err(crate::error::VerifyError::MalformedSignature)
+    );
+}
+
+#[cfg(feature = "any-algorithms")]
+#[test]
+fn account_key_verification() {
+    use crate::entities::AccountKey;
+    use crate::error::VerifyError;
+
+    let signer = secp256k1::Secp256k1::signing_only();
+    let secret_key = secp256k1::SecretKey::from_slice(&[0x42; 32]).unwrap();
+
+    let mut hasher = <tiny_keccak::Sha3 as FlowHasher>::new();
+    hasher.update("hello");
+    let signature = FlowSigner::sign(&signer, hasher, &secret_key).serialize();
+
+    let mut key = AccountKey {
+        public_key: signer
+            .serialize_public_key(&signer.to_public_key(&secret_key))
+            .into(),
+        sign_algo: Secp256k1::CODE,
+        hash_algo: Sha3::CODE,
+        weight: 1000,
+        ..Default::default()
+    };
+
+    let mut hasher = <tiny_keccak::Sha3 as FlowHasher>::new();
+    hasher.update("hello");
+    assert_eq!(
+        verify_account_key(
+            &secp256k1::Secp256k1::verification_only(),
+            &key,
+            hasher,
+            &signature
+        ),
+        Ok(())
+    );
+
+    assert_eq!(key.verify("hello", &signature), Ok(()));
+    assert_eq!(
+        key.verify("world", &signature),
+        Err(VerifyError::InvalidSignature)
+    );
+
+    let mut hasher = <sha2::Sha256 as FlowHasher>::new();
+    hasher.update("hello");
+    assert_eq!(
+        verify_account_key(&P256Verifier, &key, hasher, &signature),
+        Err(VerifyError::AlgoMismatch)
+    );
+
+    key.hash_algo = Sha2::CODE;
+    assert_eq!(
+        key.verify("hello", &signature),
+        Err(VerifyError::InvalidSignature)
+    );
+
+    key.hash_algo = Sha3::CODE;
+    key.revoked = true;
+    assert_eq!(
+        key.verify("hello", &signature),
+        Err(VerifyError::KeyRevoked)
+    );
+}
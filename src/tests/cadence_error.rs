@@ -0,0 +1,110 @@
+use crate::access::TransactionResultResponse;
+use crate::cadence_error::*;
+use crate::error::{TonicError, TransactionStatusError};
+
+const PRECONDITION_FAILED: &str = "[Error Code: 1101] error caused by: 1 error occurred:
+	* transaction execute failed: [Error Code: 1101] cadence runtime error: Execution failed:
+error: pre-condition failed: Amount withdrawn must be less than or equal than the balance of the Vault
+   --> f233dcee88fe0abe.FungibleToken:194:16
+    |
+194 |                 self.balance >= amount:
+    |                 ^^^^^^^^^^^^^^^^^^^^^^";
+
+#[test]
+fn test_parse_cadence_error() {
+    let error = CadenceError::parse(PRECONDITION_FAILED).unwrap();
+    assert_eq!(
+        CadenceError {
+            code: Some(ErrorCode::CadenceRuntime),
+            message: "pre-condition failed: Amount withdrawn must be less than or equal than the \
+                      balance of the Vault"
+                .into(),
+            location: Some("f233dcee88fe0abe.FungibleToken".into()),
+            line: Some(194),
+            column: Some(16),
+        },
+        error
+    );
+    assert!(error.is_precondition_failure());
+    assert!(!error.is_retryable());
+    assert_eq!(
+        "[Error Code: 1101] pre-condition failed: Amount withdrawn must be less than or equal \
+         than the balance of the Vault at f233dcee88fe0abe.FungibleToken:194:16",
+        error.to_string()
+    );
+
+    let error = CadenceError::parse(
+        "[Error Code: 1007] invalid proposal key: public key 0 on account f8d6e0586b0a20c7 has \
+         sequence number 4, but given 3",
+    )
+    .unwrap();
+    assert_eq!(Some(ErrorCode::InvalidProposalSeqNumber), error.code);
+    assert!(error.message.starts_with("invalid proposal key"));
+    assert_eq!(None, error.location);
+    assert!(error.is_retryable());
+
+    let out_of_gas =
+        CadenceError::parse("[Error Code: 1110] computation exceeds limit (9999)").unwrap();
+    assert!(out_of_gas.is_out_of_gas());
+
+    let storage = CadenceError::parse(
+        "[Error Code: 1103] The account with address (f8d6e0586b0a20c7) uses 100 bytes of \
+         storage which is over its capacity (10 bytes).",
+    )
+    .unwrap();
+    assert!(storage.is_storage_capacity_exceeded());
+
+    let unknown = CadenceError::parse("[Error Code: 1999] something new").unwrap();
+    assert_eq!(Some(ErrorCode::Other(1999)), unknown.code);
+    assert_eq!(1999, ErrorCode::from_code(1999).code());
+
+    let no_code = CadenceError::parse("failed to connect").unwrap();
+    assert_eq!(
+        (None, "failed to connect"),
+        (no_code.code, &*no_code.message)
+    );
+
+    assert_eq!(None, CadenceError::parse(""));
+}
+
+#[test]
+fn test_cadence_error_sources() {
+    let result = TransactionResultResponse {
+        status_code: 1,
+        error_message: PRECONDITION_FAILED.into(),
+        ..Default::default()
+    };
+    assert!(result.cadence_error().unwrap().is_precondition_failure());
+    assert_eq!(None, TransactionResultResponse::default().cadence_error());
+
+    let error = TransactionStatusError::Failed {
+        status_code: 1,
+        error_message: PRECONDITION_FAILED.into(),
+    };
+    assert_eq!(result.cadence_error(), error.cadence_error());
+    assert_eq!(None, TransactionStatusError::Expired.cadence_error());
+
+    // Script failures are reported in gRPC statuses.
+    let status = tonic::Status::invalid_argument(
+        "failed to execute script: [Error Code: 1101] cadence runtime error: Execution failed:\n\
+         error: panic: oops\n --> 0a1b2c3d:3:4",
+    );
+    let error = TonicError::Status(status).cadence_error().unwrap();
+    assert_eq!("panic: oops", error.message);
+    assert_eq!(Some("0a1b2c3d"), error.location.as_deref());
+
+    let error = TonicError::Custom(Box::new(tonic::Status::invalid_argument(
+        "[Error Code: 1113] script execution timed out",
+    )));
+    assert!(error.cadence_error().unwrap().is_retryable());
+
+    let unavailable = TonicError::Status(tonic::Status::unavailable("connection refused"));
+    assert_eq!(None, unavailable.cadence_error());
+
+    // Client errors are boxed before they reach the account.
+    let error: Box<dyn std::error::Error + Send + Sync> = Box::new(TonicError::Status(
+        tonic::Status::invalid_argument("[Error Code: 1007] invalid proposal key"),
+    ));
+    let error = CadenceError::from_error(&*error).unwrap();
+    assert_eq!(Some(ErrorCode::InvalidProposalSeqNumber), error.code);
+}
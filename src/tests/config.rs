@@ -0,0 +1,123 @@
+use super::mock::client::*;
+use super::mock::immediate_fut;
+use crate::algorithms::*;
+use crate::config::*;
+
+const CONFIG: &str = r#"{
+    "networks": {
+        "emulator": "127.0.0.1:3569",
+        "testnet": { "host": "access.devnet.nodes.onflow.org:9000", "key": "ba69f7d2e82b9edf" }
+    },
+    "accounts": {
+        "emulator-account": {
+            "address": "02",
+            "key": {
+                "type": "hex",
+                "index": 2,
+                "signatureAlgorithm": "ECDSA_secp256k1",
+                "hashAlgorithm": "SHA2_256",
+                "privateKey": "3333333333333333333333333333333333333333333333333333333333333333"
+            }
+        },
+        "env-account": {
+            "address": "0x02",
+            "key": "$FLOW_SDK_TEST_CONFIG_KEY"
+        },
+        "file-account": {
+            "address": "0x01",
+            "key": { "type": "file", "location": "./keys/file-account.pkey" }
+        }
+    },
+    "contracts": {
+        "Greeting": "./contracts/Greeting.cdc",
+        "FungibleToken": {
+            "source": "./contracts/FungibleToken.cdc",
+            "aliases": { "testnet": "9a0766d93b6608b7" }
+        }
+    },
+    "deployments": {
+        "emulator": {
+            "emulator-account": [
+                "Greeting",
+                { "name": "FungibleToken", "args": [{ "type": "String", "value": "FT" }] }
+            ]
+        }
+    }
+}"#;
+
+#[test]
+fn config_parsing() {
+    let config = Config::from_json(CONFIG).expect("Failed to parse config");
+
+    assert_eq!(config.default_network(), DEFAULT_NETWORK);
+    assert_eq!(config.network("emulator").unwrap().host, "127.0.0.1:3569");
+    assert_eq!(
+        config.network("testnet").unwrap().key.as_deref(),
+        Some("ba69f7d2e82b9edf")
+    );
+    assert!(matches!(
+        config.network("mainnet"),
+        Err(Error::NetworkNotFound(_))
+    ));
+
+    // Secured networks are not connected to in plaintext.
+    assert!(matches!(
+        immediate_fut(config.client("testnet")),
+        Err(Error::SecureNetworkUnsupported(network)) if network == "testnet"
+    ));
+
+    let env_account = config.account_config("env-account").unwrap();
+
+    assert_eq!(
+        env_account.key.source,
+        KeySource::Env("FLOW_SDK_TEST_CONFIG_KEY".into())
+    );
+    assert_eq!(env_account.key.sign_algo, AnySignatureAlgorithm::P256);
+    assert_eq!(env_account.key.hash_algo, AnyHashAlgorithm::Sha3);
+
+    assert_eq!(
+        config.account_config("file-account").unwrap().key.source,
+        KeySource::File("./keys/file-account.pkey".into())
+    );
+
+    assert_eq!(
+        config.contract_address("FungibleToken", "testnet"),
+        Some(&[0x9a, 0x07, 0x66, 0xd9, 0x3b, 0x66, 0x08, 0xb7][..])
+    );
+    assert_eq!(
+        config.contract_address("Greeting", "emulator"),
+        Some(&[0x02][..])
+    );
+    assert_eq!(config.contract_address("Greeting", "testnet"), None);
+
+    let deployments = &config.deployments["emulator"]["emulator-account"];
+
+    assert_eq!(deployments[0].name, "Greeting");
+    assert_eq!(deployments[1].args.len(), 1);
+}
+
+#[test]
+fn config_accounts() {
+    let config = Config::from_json(CONFIG).unwrap();
+
+    let account = immediate_fut(config.account_with("emulator-account", MockClient))
+        .expect("Failed to log in");
+
+    assert_eq!(account.address(), [0x02]);
+    assert_eq!(account.primary_key_id(), 2);
+
+    std::env::set_var("FLOW_SDK_TEST_CONFIG_KEY", hex::encode(ACC02_P256_KEY));
+    let secret_key = config.secret_key("env-account").unwrap();
+    std::env::remove_var("FLOW_SDK_TEST_CONFIG_KEY");
+
+    assert_eq!(*secret_key.to_bytes(), ACC02_P256_KEY);
+
+    assert!(matches!(
+        config.secret_key("env-account"),
+        Err(Error::EnvVarNotFound(_))
+    ));
+    assert!(matches!(
+        config.secret_key("file-account"),
+        Err(Error::Io(_))
+    ));
+}
@@ -0,0 +1,121 @@
+use super::mock::client::*;
+use super::mock::immediate_fut;
+use crate::account::AnyAccount;
+use crate::algorithms::*;
+use crate::client::FlowClient;
+use crate::multi::fcl::*;
+use crate::multi::interchange::PortableParty;
+use crate::multi::session::SigningSession;
+use crate::multi::{PartyBuilder, PartySigner, SigningParty};
+use crate::sign::{One, SignMethod};
+use crate::transaction::SignatureE;
+
+#[test]
+fn test_fcl_authz() {
+    let secret_key =
+        AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY).unwrap();
+    let payer = immediate_fut(AnyAccount::new(MockClient, &[0x02], secret_key))
+        .expect("Failed to create AnyAccount");
+    let service = AuthzService::new(payer, 2, "https://example.com/authz");
+
+    // The user at 0x03 has the same keys as 0x02.
+    let user = unsafe {
+        AnyAccount::new_unchecked(
+            MockClient,
+            [0x03].into(),
+            SignMethod::One(One {
+                key_id: 2,
+                key: AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY)
+                    .unwrap(),
+            }),
+        )
+    };
+
+    // The arguments are kept as they are, even if they would be serialized differently.
+    let mut party = PartyBuilder::new()
+        .script("transaction(greeting: String) { prepare(signer: AuthAccount) {} }")
+        .argument_raw(&br#"{"value":"Hello","type":"String"}"#[..])
+        .reference_block([0x42; 32])
+        .proposer_address([0x03])
+        .proposal_key_id(2)
+        .proposal_key_sequence_number(3)
+        .payer([0x02])
+        .authorizer([0x03])
+        .build();
+    immediate_fut(user.sign_payload(&mut party)).unwrap();
+
+    let voucher = Voucher::try_from(&party).unwrap();
+    let json = serde_json::to_string(&voucher).unwrap();
+    assert!(json.contains(r#""arguments":[{"value":"Hello","type":"String"}]"#));
+    let voucher: Voucher = serde_json::from_str(&json).unwrap();
+    assert_eq!(party, SigningParty::try_from(&voucher).unwrap());
+
+    // Pre-authorization answers with the service for the roles asked.
+    let pre_signable = serde_json::json!({
+        "f_type": "PreSignable",
+        "f_vsn": "1.0.1",
+        "roles": { "proposer": false, "authorizer": false, "payer": true },
+        "cadence": voucher.cadence,
+        "voucher": voucher,
+    });
+    let response: PollingResponse<PreAuthzResponse> =
+        serde_json::from_str(&service.pre_authz_json(&pre_signable.to_string()).unwrap()).unwrap();
+    assert_eq!(PollingStatus::Approved, response.status);
+    let services = response.data.unwrap();
+    assert_eq!(None, services.proposer);
+    assert!(services.authorization.is_empty());
+    assert_eq!(vec![service.service()], services.payer);
+    assert_eq!("0x02", services.payer[0].identity.address);
+    assert_eq!(2, services.payer[0].identity.key_id);
+
+    // Authorization signs the envelope as the payer.
+    let portable = PortableParty::new(party.clone());
+    let signable = Signable::new(&portable, &[0x02], 2).unwrap();
+    assert!(signable.roles.payer && !signable.roles.authorizer);
+    let response: PollingResponse<CompositeSignature> = serde_json::from_str(
+        &immediate_fut(service.authz_json(&serde_json::to_string(&signable).unwrap())).unwrap(),
+    )
+    .unwrap();
+    let signature = response.data.unwrap();
+    assert_eq!("0x02", signature.addr);
+    assert_eq!(2, signature.key_id);
+
+    let accounts = [[0x02], [0x03]].map(|address| {
+        let mut account =
+            immediate_fut(FlowClient::new(MockClient).account_at_latest_block(&[0x02])).unwrap();
+        account.address = address.into();
+        account
+    });
+    let mut session = SigningSession::with_accounts(party, accounts).unwrap();
+    let mut sig = [0; 64];
+    hex::decode_to_slice(&signature.signature, &mut sig).unwrap();
+    session
+        .add_envelope_signature(SignatureE {
+            address: [0x02].into(),
+            key_id: 2,
+            signature: sig,
+        })
+        .unwrap();
+
+    // Messages that do not match the voucher are not signed.
+    let mut tampered = signable.clone();
+    tampered.message = hex::encode(b"Sign me instead");
+    assert!(matches!(
+        immediate_fut(service.authz(&tampered)),
+        Err(Error::MessageMismatch)
+    ));
+
+    let mut other_key = signable.clone();
+    other_key.key_id = 0;
+    assert!(matches!(
+        immediate_fut(service.authz(&other_key)),
+        Err(Error::KeyNotFound(0))
+    ));
+
+    let other_signer = Signable::new(&portable, &[0x03], 2).unwrap();
+    assert!(other_signer.roles.proposer && other_signer.roles.authorizer);
+    assert!(matches!(
+        immediate_fut(service.authz(&other_signer)),
+        Err(Error::WrongSigner)
+    ));
+}
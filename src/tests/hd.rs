@@ -0,0 +1,109 @@
+use crate::hd::*;
+
+const BIP32_SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+fn path(s: &str) -> DerivationPath {
+    s.parse().unwrap()
+}
+
+#[test]
+fn mnemonic_seed() {
+    let mnemonic = Mnemonic::parse(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+    )
+    .unwrap();
+    let seed = Seed::from_mnemonic(&mnemonic, "TREZOR");
+
+    assert_eq!(
+        hex::encode(seed.as_bytes()),
+        "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+    );
+
+    assert!(Mnemonic::parse("abandon abandon abandon").is_err());
+
+    let generated = generate_mnemonic(24).unwrap();
+    assert_eq!(generated.word_count(), 24);
+    assert!(generate_mnemonic(13).is_err());
+}
+
+#[test]
+fn derivation_path() {
+    assert_eq!(path("m/44'/539'/0'/0/0"), DerivationPath::flow(0, 0));
+    assert_eq!(path("m/44H/539h/1'/0/2"), DerivationPath::flow(1, 2));
+    assert_eq!(DerivationPath::flow(0, 0).to_string(), "m/44'/539'/0'/0/0");
+    assert!(path("m").children().is_empty());
+
+    for invalid in ["", "44'/0", "m/", "m/x", "m/2147483648"] {
+        assert!(invalid.parse::<DerivationPath>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn derive_secp256k1() {
+    let seed = Seed::new(hex::decode(BIP32_SEED).unwrap());
+
+    for (p, expected) in [
+        (
+            "m",
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+        ),
+        (
+            "m/0'",
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+        ),
+        (
+            "m/0'/1",
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+        ),
+        (
+            "m/0'/1/2'",
+            "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+        ),
+        (
+            "m/0'/1/2'/2",
+            "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+        ),
+        (
+            "m/0'/1/2'/2/1000000000",
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+        ),
+    ] {
+        let key = seed.derive_secp256k1(&path(p)).unwrap();
+        assert_eq!(hex::encode(&key[..]), expected, "{}", p);
+    }
+}
+
+#[test]
+fn derive_p256() {
+    let seed = Seed::new(hex::decode(BIP32_SEED).unwrap());
+
+    for (p, expected) in [
+        (
+            "m",
+            "612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2",
+        ),
+        (
+            "m/0'",
+            "6939694369114c67917a182c59ddb8cafc3004e63ca5d3b84403ba8613debc0c",
+        ),
+        (
+            "m/0'/1",
+            "284e9d38d07d21e4e281b645089a94f4cf5a5a81369acf151a1c3a57f18b2129",
+        ),
+        (
+            "m/0'/1/2'",
+            "694596e8a54f252c960eb771a3c41e7e32496d03b954aeb90f61635b8e092aa7",
+        ),
+        (
+            "m/0'/1/2'/2",
+            "5996c37fd3dd2679039b23ed6f70b506c6b56b3cb5e424681fb0fa64caf82aaa",
+        ),
+        (
+            "m/0'/1/2'/2/1000000000",
+            "21c4f269ef0a5fd1badf47eeacebeeaa3de22eb8e5b0adcd0f27dd99d34d0119",
+        ),
+    ] {
+        let key = seed.derive_p256(&path(p)).unwrap();
+        assert_eq!(hex::encode(key.to_bytes()), expected, "{}", p);
+    }
+}
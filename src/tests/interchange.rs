@@ -0,0 +1,182 @@
+use super::mock::algorithms::*;
+use super::mock::client::*;
+use super::mock::immediate_fut;
+use crate::account::{Account, AnyAccount};
+use crate::algorithms::*;
+use crate::multi::interchange::{Error, PortableParty};
+use crate::multi::PartyBuilder;
+
+#[test]
+fn test_portable_party() {
+    let secret_key =
+        AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY).unwrap();
+    let authorizer = immediate_fut(AnyAccount::new(MockClient, &[0x02], secret_key))
+        .expect("Failed to create AnyAccount");
+
+    let party = PartyBuilder::new()
+        .script("transaction { prepare(signer: AuthAccount) {} }")
+        .argument_raw(&br#"{"type":"Int","value":"42"}"#[..])
+        .reference_block([0x42; 32])
+        .gas_limit(9999)
+        .proposer_address([0x02])
+        .proposal_key_id(2)
+        .proposal_key_sequence_number(3)
+        .payer([0x01])
+        .authorizer([0x02])
+        .build();
+    let original = PortableParty::new(party);
+
+    let exported = original.export_json();
+    let mut imported = PortableParty::import_json(&exported).unwrap();
+    assert_eq!(original, imported);
+
+    immediate_fut(imported.sign(&authorizer)).unwrap();
+    assert_eq!(1, imported.party().payload_signatures().len());
+
+    let mut merged = original.clone();
+    merged.merge(imported.clone()).unwrap();
+    assert_eq!(imported, merged);
+
+    // Signatures that are already collected are not added again.
+    merged.merge(imported.clone()).unwrap();
+    assert_eq!(imported, merged);
+
+    // The RLP encoding pads addresses to 8 bytes.
+    let rlp_hex = merged.export_rlp_hex();
+    let from_rlp = PortableParty::import_rlp_hex(&rlp_hex).unwrap();
+    assert_eq!(rlp_hex, from_rlp.export_rlp_hex());
+    assert_eq!(
+        [0, 0, 0, 0, 0, 0, 0, 0x02],
+        from_rlp.party().payload_signatures()[0].address[..]
+    );
+
+    let mut merged_from_rlp = original.clone();
+    merged_from_rlp.merge(from_rlp).unwrap();
+    assert_eq!(merged, merged_from_rlp);
+
+    let other = PortableParty::new(
+        PartyBuilder::new()
+            .script("transaction {}")
+            .reference_block([0x42; 32])
+            .proposer_address([0x02])
+            .proposal_key_id(2)
+            .proposal_key_sequence_number(3)
+            .payer([0x01])
+            .build(),
+    );
+    assert!(matches!(
+        merged.clone().merge(other),
+        Err(Error::PayloadMismatch)
+    ));
+
+    // Payload signatures cannot be added once the envelope is signed.
+    let payer = immediate_fut(Account::<_, MockKey, MockSigner, TruncatingHasher>::new(
+        MockClient,
+        &[0x01],
+        ACC01_KEY,
+    ))
+    .expect("Failed to create MockAccount");
+    let mut enveloped = original.clone();
+    immediate_fut(enveloped.sign(&payer)).unwrap();
+    assert!(matches!(
+        immediate_fut(enveloped.sign(&authorizer)),
+        Err(Error::EnvelopeSigned)
+    ));
+    assert!(matches!(
+        enveloped.clone().merge(imported.clone()),
+        Err(Error::EnvelopeSigned)
+    ));
+    assert!(matches!(
+        imported.clone().merge(enveloped),
+        Err(Error::EnvelopeSigned)
+    ));
+
+    let mut complete = imported.clone();
+    immediate_fut(complete.sign(&payer)).unwrap();
+    let mut merged_complete = original.clone();
+    merged_complete.merge(complete.clone()).unwrap();
+    merged_complete.merge(imported.clone()).unwrap();
+    assert_eq!(complete, merged_complete);
+
+    let unsupported = exported.replacen("\"version\": 1", "\"version\": 2", 1);
+    assert!(matches!(
+        PortableParty::import_json(&unsupported),
+        Err(Error::UnsupportedVersion(2))
+    ));
+
+    let transaction = merged.into_transaction();
+    assert_eq!(1, transaction.payload_signatures.len());
+    assert!(transaction.envelope_signatures.is_empty());
+}
+
+#[test]
+fn test_decoded_transactions() {
+    use otopr::Repeated;
+
+    use crate::multi::SigningParty;
+    use crate::transaction::{ProposalKeyD, SignatureD, TransactionD};
+
+    let secret_key =
+        AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY).unwrap();
+    let authorizer = immediate_fut(AnyAccount::new(MockClient, &[0x02], secret_key))
+        .expect("Failed to create AnyAccount");
+
+    let mut party = PartyBuilder::new()
+        .script("transaction { prepare(signer: AuthAccount) {} }")
+        .reference_block([0x42; 32])
+        .proposer_address([0x02])
+        .proposal_key_id(2)
+        .proposal_key_sequence_number(3)
+        .payer([0x01])
+        .authorizer([0x02])
+        .build();
+    authorizer.sign_party(&mut party);
+
+    let transaction = PortableParty::new(party.clone()).into_transaction();
+    let decoded = TransactionD {
+        script: transaction.script,
+        arguments: Repeated::new(transaction.arguments),
+        reference_block_id: transaction.reference_block_id,
+        gas_limit: transaction.gas_limit,
+        proposal_key: ProposalKeyD {
+            address: transaction.proposal_key.address,
+            key_id: transaction.proposal_key.key_id,
+            sequence_number: transaction.proposal_key.sequence_number,
+        },
+        payer: transaction.payer,
+        authorizers: Repeated::new(transaction.authorizers),
+        payload_signatures: Repeated::new(
+            transaction
+                .payload_signatures
+                .into_iter()
+                .map(|signature| SignatureD {
+                    address: signature.address,
+                    key_id: signature.key_id,
+                    signature: signature.signature.into(),
+                })
+                .collect(),
+        ),
+        envelope_signatures: Repeated::new(Vec::new()),
+    };
+    assert_eq!(party, SigningParty::try_from(decoded).unwrap());
+
+    let rlp = hex::decode(PortableParty::new(party.clone()).export_rlp_hex()).unwrap();
+    let decoded = SigningParty::decode_rlp(&rlp).unwrap();
+    assert_eq!(
+        party.payload_signatures().len(),
+        decoded.payload_signatures().len()
+    );
+
+    let mut merged = PortableParty::new(party);
+    merged.merge(PortableParty::new(decoded)).unwrap();
+    assert_eq!(1, merged.party().payload_signatures().len());
+
+    let invalid = TransactionD {
+        script: [0xff].into(),
+        ..Default::default()
+    };
+    assert!(matches!(
+        SigningParty::try_from(invalid),
+        Err(Error::InvalidField("script"))
+    ));
+}
@@ -0,0 +1,94 @@
+use super::mock::client::*;
+use super::mock::immediate_fut;
+use crate::algorithms::*;
+use crate::keystore::*;
+
+const PARAMS: KdfParams = KdfParams {
+    log_n: 4,
+    r: 8,
+    p: 1,
+};
+
+fn metadata(key_index: u32) -> KeystoreMetadata {
+    KeystoreMetadata {
+        address: [0x02].into(),
+        key_index,
+        sign_algo: AnySignatureAlgorithm::Secp256k1,
+        hash_algo: AnyHashAlgorithm::Sha2,
+    }
+}
+
+#[test]
+fn keystore_roundtrip() {
+    let keystore = Keystore::encrypt(&ACC02_FULL_WEIGHT_KEY, metadata(2), "hunter2", PARAMS)
+        .expect("Failed to encrypt");
+
+    let path = std::env::temp_dir().join(format!("flow-sdk-keystore-{}.json", std::process::id()));
+    std::fs::write(&path, "").unwrap();
+    keystore.save(&path).unwrap();
+    let loaded = Keystore::load(&path).unwrap();
+
+    // Existing files are only left readable by their owner.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(0o600, mode & 0o777);
+    }
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(keystore, loaded);
+
+    let secret_key = loaded.decrypt("hunter2").unwrap();
+
+    assert_eq!(*secret_key.to_bytes(), ACC02_FULL_WEIGHT_KEY);
+    assert_eq!(secret_key.hash_algo(), AnyHashAlgorithm::Sha2);
+
+    assert!(matches!(loaded.decrypt("hunter3"), Err(Error::Decryption)));
+
+    let tampered = loaded.to_json().replace("\"SHA2_256\"", "\"SHA3_256\"");
+    let tampered = Keystore::from_json(&tampered).unwrap();
+
+    assert!(matches!(
+        tampered.decrypt("hunter2"),
+        Err(Error::Decryption)
+    ));
+
+    // Costly parameters are rejected before deriving the key.
+    let costly = loaded.to_json().replace("\"log_n\": 4", "\"log_n\": 30");
+    assert!(matches!(
+        Keystore::from_json(&costly),
+        Err(Error::InvalidParams)
+    ));
+    let costly = KdfParams { p: 17, ..PARAMS };
+    assert!(matches!(
+        Keystore::encrypt(&ACC02_FULL_WEIGHT_KEY, metadata(2), "hunter2", costly),
+        Err(Error::InvalidParams)
+    ));
+}
+
+#[test]
+fn keystore_unlock() {
+    let keystore =
+        Keystore::encrypt(&ACC02_FULL_WEIGHT_KEY, metadata(2), "hunter2", PARAMS).unwrap();
+    let account = immediate_fut(keystore.unlock(MockClient, "hunter2")).expect("Failed to unlock");
+
+    assert_eq!(account.address(), [0x02]);
+    assert_eq!(account.primary_key_id(), 2);
+
+    let keystore =
+        Keystore::encrypt(&ACC02_FULL_WEIGHT_KEY, metadata(0), "hunter2", PARAMS).unwrap();
+    let res = immediate_fut(keystore.unlock(MockClient, "hunter2"));
+
+    assert!(matches!(
+        res,
+        Err(Error::KeyIndexMismatch {
+            expected: 0,
+            found: 2
+        })
+    ));
+
+    let res = Keystore::encrypt(&[0; 32], metadata(2), "hunter2", PARAMS);
+
+    assert!(matches!(res, Err(Error::SecretKey(_))));
+}
@@ -0,0 +1,121 @@
+use cadence_json::ValueRef;
+
+use super::mock::algorithms::*;
+use super::mock::client::*;
+use super::mock::immediate_fut;
+use crate::account::Account;
+use crate::error::{LimitError, SendTransactionError};
+use crate::multi::PartyBuilder;
+use crate::transaction::{TransactionHeaderBuilder, TransactionLimits};
+
+#[test]
+fn test_transaction_limits() {
+    let limits = TransactionLimits {
+        max_script_size: 32,
+        max_argument_count: 1,
+        max_argument_size: 32,
+        ..TransactionLimits::new()
+    };
+
+    let header = TransactionHeaderBuilder::new()
+        .script_static("transaction(a: Int) {}")
+        .argument(ValueRef::Int(1.into()))
+        .build();
+    assert_eq!(Ok(()), limits.check_header(&header));
+    let too_large = TransactionHeaderBuilder::new()
+        .script_static("transaction(a: String) {}")
+        .argument(ValueRef::String("larger than the maximum size"))
+        .build();
+    assert!(matches!(
+        limits.check_header(&too_large),
+        Err(LimitError::ArgumentTooLarge {
+            index: 0,
+            max: 32,
+            ..
+        })
+    ));
+
+    let party = |gas_limit, script: &str| {
+        PartyBuilder::new()
+            .script(script)
+            .reference_block([0x42; 32])
+            .gas_limit(gas_limit)
+            .proposer_address([0x02])
+            .proposal_key_id(0)
+            .proposal_key_sequence_number(1)
+            .payer([0x02])
+            .authorizer([0x02])
+            .build()
+    };
+    assert_eq!(Ok(()), limits.check_party(&party(9999, "transaction {}")));
+    assert_eq!(
+        Err(LimitError::GasLimitTooHigh {
+            gas_limit: 10000,
+            max: 9999
+        }),
+        limits.check_party(&party(10000, "transaction {}"))
+    );
+    assert_eq!(
+        Err(LimitError::EmptyScript),
+        limits.check_party(&party(1000, ""))
+    );
+    assert!(matches!(
+        limits.check_party(&party(1000, &"a".repeat(33))),
+        Err(LimitError::ScriptTooLarge { size: 33, max: 32 })
+    ));
+
+    // The size of the transaction is its encoded size, which is larger than its script.
+    let small = TransactionLimits {
+        max_transaction_size: 64,
+        ..TransactionLimits::new()
+    };
+    assert!(matches!(
+        small.check_party(&party(1000, &"a".repeat(40))),
+        Err(LimitError::TransactionTooLarge { max: 64, .. })
+    ));
+}
+
+#[test]
+fn test_send_checks_limits() {
+    let mut account = immediate_fut(Account::<_, MockKey, MockSigner, TruncatingHasher>::new(
+        MockClient,
+        &[0x01],
+        ACC01_KEY,
+    ))
+    .expect("Failed to create MockAccount");
+
+    let header = TransactionHeaderBuilder::new()
+        .script_static("transaction(a: Int, b: Int) {}")
+        .argument(ValueRef::Int(1.into()))
+        .argument(ValueRef::Int(2.into()))
+        .build();
+
+    account.set_transaction_limits(Some(TransactionLimits {
+        max_argument_count: 1,
+        ..TransactionLimits::new()
+    }));
+    assert!(matches!(
+        immediate_fut(account.send_transaction_header(&header)),
+        Err(SendTransactionError::Limit(LimitError::TooManyArguments {
+            count: 2,
+            max: 1
+        }))
+    ));
+
+    // The header fits, but not with the reference block, the addresses and the signature.
+    let small = TransactionLimits {
+        max_transaction_size: 128,
+        ..TransactionLimits::new()
+    };
+    assert_eq!(Ok(()), small.check_header(&header));
+    account.set_transaction_limits(Some(small));
+    assert!(matches!(
+        immediate_fut(account.send_transaction_header(&header)),
+        Err(SendTransactionError::Limit(
+            LimitError::TransactionTooLarge { max: 128, .. }
+        ))
+    ));
+
+    account.set_transaction_limits(None);
+    assert!(immediate_fut(account.send_transaction_header(&header)).is_ok());
+}
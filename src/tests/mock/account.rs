@@ -2,8 +2,11 @@ use super::algorithms::*;
 use super::client::*;
 use super::immediate_fut;
 use crate::account::*;
+#[cfg(feature = "any-algorithms")]
+use crate::algorithms::*;
 
 type MockAccount = Account<MockClient, MockKey, MockSigner, MockHasher>;
+type TruncatingAccount = Account<MockClient, MockKey, MockSigner, TruncatingHasher>;
 
 #[test]
 fn test_account_new() {
@@ -33,3 +36,417 @@ fn test_account_new() {
 
     assert!(matches!(res, Err(Error::KeyRevoked)));
 }
+
+#[test]
+fn test_sign_async() {
+    let account = immediate_fut(MockAccount::new(MockClient, &[0x01], ACC01_KEY))
+        .expect("Failed to create MockAccount");
+
+    let data = "Sign me! Sign me! Sign me!";
+    let signatures = immediate_fut(account.sign_data_async(data)).unwrap();
+
+    assert_eq!(signatures, account.sign_data(data).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_create_account() {
+    let mut account = immediate_fut(TruncatingAccount::new(MockClient, &[0x01], ACC01_KEY))
+        .expect("Failed to create MockAccount");
+
+    let address = immediate_fut(account.create_account(&[ACC01_KEY], Default::default()))
+        .expect("Failed to create account");
+
+    assert_eq!(&*address, &[0, 0, 0, 0, 0, 0, 0, 0x02]);
+}
+
+#[cfg(feature = "any-algorithms")]
+#[test]
+fn test_any_account_multisign() {
+    let secp256k1 = AnySecretKey::new(Secp256k1::CODE, Sha3::CODE, &ACC02_SECP256K1_KEY).unwrap();
+    let p256 = AnySecretKey::new(P256::CODE, Sha2::CODE, &ACC02_P256_KEY).unwrap();
+
+    let account = immediate_fut(AnyAccount::new_multisign(
+        MockClient,
+        &[0x02],
+        0,
+        &[secp256k1, p256.clone()],
+    ))
+    .expect("Failed to create AnyAccount");
+
+    let data = b"Sign me with both keys!";
+    let signatures: Vec<_> = account.sign_data(data).map(|sig| sig.serialize()).collect();
+
+    let mut sha3 = <tiny_keccak::Sha3 as FlowHasher>::new();
+    FlowHasher::update(&mut sha3, data);
+    let secp256k1_sk = secp256k1::SecretKey::from_slice(&ACC02_SECP256K1_KEY).unwrap();
+    let expected_secp256k1 =
+        FlowSigner::sign(&secp256k1::Secp256k1::signing_only(), sha3, &secp256k1_sk);
+
+    let mut sha2 = <sha2::Sha256 as FlowHasher>::new();
+    FlowHasher::update(&mut sha2, data);
+    let p256_sk = p256::ecdsa::SigningKey::from_slice(&ACC02_P256_KEY).unwrap();
+    let expected_p256 = P256Signer.sign(sha2, &p256_sk);
+
+    assert_eq!(
+        signatures,
+        [expected_secp256k1.serialize(), expected_p256.serialize()]
+    );
+
+    let wrong_hash = AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_SECP256K1_KEY).unwrap();
+
+    let res = immediate_fut(AnyAccount::new_multisign(
+        MockClient,
+        &[0x02],
+        0,
+        &[wrong_hash, p256],
+    ));
+
+    assert!(matches!(res, Err(Error::AlgoMismatch)));
+}
+
+#[cfg(feature = "any-algorithms")]
+#[test]
+fn test_verify_user_signatures() {
+    use crate::client::FlowClient;
+    use crate::error::{VerifyError, VerifySignaturesError};
+    use crate::message::verify_user_signatures;
+
+    let secp256k1 = AnySecretKey::new(Secp256k1::CODE, Sha3::CODE, &ACC02_SECP256K1_KEY).unwrap();
+    let p256 = AnySecretKey::new(P256::CODE, Sha2::CODE, &ACC02_P256_KEY).unwrap();
+
+    let account = immediate_fut(AnyAccount::new_multisign(
+        MockClient,
+        &[0x02],
+        0,
+        &[secp256k1, p256],
+    ))
+    .expect("Failed to create AnyAccount");
+
+    let message = b"Hello from flow.rs!";
+    let signatures: Vec<_> = account.sign_user_message(message).collect();
+    let mut client = FlowClient::new(MockClient);
+
+    let res = immediate_fut(verify_user_signatures(
+        &mut client,
+        &[0x02],
+        message,
+        signatures.iter().copied(),
+    ));
+    assert!(res.is_ok(), "{:?}", res);
+
+    let res = immediate_fut(verify_user_signatures(
+        &mut client,
+        &[0x02],
+        b"Another message",
+        signatures.iter().copied(),
+    ));
+    assert!(matches!(
+        res,
+        Err(VerifySignaturesError::InvalidSignature {
+            key_id: 0,
+            error: VerifyError::InvalidSignature
+        })
+    ));
+
+    let res = immediate_fut(verify_user_signatures(
+        &mut client,
+        &[0x02],
+        message,
+        signatures.iter().copied().take(1),
+    ));
+    assert!(matches!(
+        res,
+        Err(VerifySignaturesError::NotEnoughWeight(500))
+    ));
+
+    let res = immediate_fut(verify_user_signatures(
+        &mut client,
+        &[0x02],
+        message,
+        [signatures[0], signatures[0]],
+    ));
+    assert!(matches!(res, Err(VerifySignaturesError::DuplicateKey(0))));
+}
+
+#[cfg(feature = "any-algorithms")]
+#[test]
+fn test_account_proof() {
+    use crate::client::FlowClient;
+    use crate::error::VerifySignaturesError;
+    use crate::message::{encode_account_proof, verify_account_proof};
+
+    assert_eq!(
+        hex::encode(encode_account_proof(
+            "AWESOME-APP-ID",
+            hex::decode("abc123def456").unwrap(),
+            b"076a4cc9d5db301f6bb921af4e4a196b",
+        )),
+        "f8398e415745534f4d452d4150502d4944880000abc123def456a0\
+         3037366134636339643564623330316636626239323161663465346131393662"
+    );
+
+    let secp256k1 = AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY).unwrap();
+    let account = immediate_fut(AnyAccount::new(MockClient, &[0x02], secp256k1))
+        .expect("Failed to create AnyAccount");
+
+    let nonce = [0x42; 32];
+    let signatures: Vec<_> = account.sign_account_proof("flow.rs", nonce).collect();
+    let mut client = FlowClient::new(MockClient);
+
+    let res = immediate_fut(verify_account_proof(
+        &mut client,
+        "flow.rs",
+        &[0x02],
+        nonce,
+        signatures.iter().copied(),
+    ));
+    assert!(res.is_ok(), "{:?}", res);
+
+    let res = immediate_fut(verify_account_proof(
+        &mut client,
+        "another app",
+        &[0x02],
+        nonce,
+        signatures.iter().copied(),
+    ));
+    assert!(matches!(
+        res,
+        Err(VerifySignaturesError::InvalidSignature { key_id: 2, .. })
+    ));
+
+    let res = immediate_fut(verify_account_proof(
+        &mut client,
+        "flow.rs",
+        &[0x02],
+        [0x42; 16],
+        signatures.iter().copied(),
+    ));
+    assert!(matches!(res, Err(VerifySignaturesError::NonceTooShort)));
+
+    let res = immediate_fut(verify_account_proof(
+        &mut client,
+        "flow.rs",
+        &[0x02; 9],
+        nonce,
+        signatures.iter().copied(),
+    ));
+    assert!(matches!(res, Err(VerifySignaturesError::InvalidAddress)));
+}
+
+#[cfg(feature = "any-algorithms")]
+#[test]
+fn test_send_transaction_header_with() {
+    use crate::access::SendTransactionResponse;
+    use crate::transaction::TransactionHeaderBuilder;
+
+    let secp256k1 = AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY).unwrap();
+    let mut account = immediate_fut(AnyAccount::new(
+        RecordingClient::default(),
+        &[0x02],
+        secp256k1,
+    ))
+    .expect("Failed to create AnyAccount");
+
+    let payer = immediate_fut(TruncatingAccount::new(MockClient, &[0x01], ACC01_KEY))
+        .expect("Failed to create MockAccount");
+
+    let header = TransactionHeaderBuilder::new()
+        .script_static("transaction {}")
+        .build();
+
+    let options = SendOptions::new()
+        .gas_limit(9999)
+        .reference_block([0x24; 32])
+        .proposal_key_id(2)
+        .sequence_number(11)
+        .payer(&payer);
+
+    let res = immediate_fut(account.send_transaction_header_with(&header, options));
+    assert!(matches!(
+        res,
+        Ok(SendTransactionResponse { ref id }) if **id == MOCK_TRANSACTION_ID
+    ));
+
+    let sent = &account.client().inner_mut().sent;
+    assert_eq!(sent.len(), 1);
+
+    let transaction = &sent[0];
+    assert_eq!(transaction.gas_limit, 9999);
+    assert_eq!(*transaction.reference_block_id, [0x24; 32]);
+    assert_eq!(*transaction.proposal_key.address, [0x02]);
+    assert_eq!(transaction.proposal_key.key_id, 2);
+    assert_eq!(transaction.proposal_key.sequence_number, 11);
+    assert_eq!(*transaction.payer, [0x01]);
+    assert_eq!(transaction.authorizers.len(), 1);
+    assert_eq!(*transaction.authorizers[0], [0x02]);
+
+    // The sender signs the payload, and the payer signs the envelope.
+    assert_eq!(transaction.payload_signatures.len(), 1);
+    assert_eq!(*transaction.payload_signatures[0].address, [0x02]);
+    assert_eq!(transaction.payload_signatures[0].key_id, 2);
+    assert_eq!(transaction.envelope_signatures.len(), 1);
+    assert_eq!(*transaction.envelope_signatures[0].address, [0x01]);
+    assert_eq!(transaction.envelope_signatures[0].key_id, 0);
+}
+
+#[cfg(feature = "any-algorithms")]
+#[test]
+fn test_sequence_number_tracking() {
+    use crate::access::TransactionResultResponse;
+    use crate::error::SendTransactionError;
+    use crate::sign::{One, SignMethod};
+    use crate::transaction::{TransactionHeaderBuilder, TransactionStatus};
+
+    let secp256k1 = AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY).unwrap();
+    let mut account = immediate_fut(AnyAccount::new(MockClient, &[0x02], secp256k1))
+        .expect("Failed to create AnyAccount");
+    account.track_sequence_number(true);
+    assert_eq!(None, account.tracked_sequence_number());
+
+    let header = TransactionHeaderBuilder::new()
+        .script_static("transaction {}")
+        .build();
+
+    immediate_fut(account.send_transaction_header(&header)).unwrap();
+    assert_eq!(Some(4), account.tracked_sequence_number());
+
+    immediate_fut(account.send_transaction_header_with(&header, SendOptions::new())).unwrap();
+    assert_eq!(Some(5), account.tracked_sequence_number());
+
+    // Explicit sequence numbers are not tracked.
+    let options = SendOptions::new().sequence_number(3);
+    immediate_fut(account.send_transaction_header_with(&header, options)).unwrap();
+    assert_eq!(Some(5), account.tracked_sequence_number());
+
+    account.update_sequence_number(&TransactionResultResponse {
+        status: TransactionStatus::Sealed,
+        status_code: 1,
+        error_message: "[Error Code: 1007] invalid proposal key".into(),
+        events: Default::default(),
+        block_id: Default::default(),
+    });
+    assert_eq!(None, account.tracked_sequence_number());
+    assert_eq!(
+        Ok(3),
+        immediate_fut(account.primary_key_sequence_number()).map_err(|e| e.to_string())
+    );
+    assert_eq!(Some(3), account.tracked_sequence_number());
+
+    // The key of 0x01 is not on 0x02.
+    let mut wrong_address = unsafe {
+        MockAccount::new_unchecked(
+            MockClient,
+            [0x02].into(),
+            SignMethod::One(One {
+                key_id: 0,
+                key: ACC01_KEY,
+            }),
+        )
+    };
+    assert!(matches!(
+        immediate_fut(wrong_address.primary_key_sequence_number()),
+        Err(SendTransactionError::ProposalKeyNotFound(0))
+    ));
+
+    account.track_sequence_number(false);
+    immediate_fut(account.send_transaction_header(&header)).unwrap();
+    assert_eq!(None, account.tracked_sequence_number());
+}
+
+#[cfg(feature = "any-algorithms")]
+#[tokio::test]
+async fn test_send_transaction_header_resubmit() {
+    use std::time::Duration;
+
+    use crate::error::ResubmitError;
+    use crate::transaction::{TransactionHeaderBuilder, TransactionStatus::*};
+
+    let header = TransactionHeaderBuilder::new()
+        .script_static("transaction {}")
+        .build();
+    let account = |statuses| {
+        let secp256k1 =
+            AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY).unwrap();
+        let mut account = immediate_fut(AnyAccount::new(
+            ScriptedClient::new(statuses),
+            &[0x02],
+            secp256k1,
+        ))
+        .expect("Failed to create AnyAccount");
+        account.track_sequence_number(true);
+        account
+    };
+
+    // Expired transactions and transactions rejected because of their proposal key are resubmitted.
+    let mut resubmitting = account(&[(Pending, 0), (Expired, 0), (Sealed, 1007)]);
+    let options = ResubmitOptions {
+        delay: Duration::from_millis(1),
+        ..Default::default()
+    };
+    let resubmitted = resubmitting
+        .send_transaction_header_resubmit(&header, SendOptions::new(), options)
+        .await
+        .unwrap();
+    assert_eq!(vec![3], resubmitted.id);
+    assert_eq!(3, resubmitted.attempts);
+    assert_eq!(Sealed, resubmitted.result.status);
+    // The sequence number was queried again for the last attempt.
+    assert_eq!(Some(4), resubmitting.tracked_sequence_number());
+
+    let mut expiring = account(&[(Expired, 0), (Expired, 0)]);
+    let options = ResubmitOptions {
+        max_attempts: 2,
+        ..Default::default()
+    };
+    assert!(matches!(
+        expiring.send_transaction_header_resubmit(&header, SendOptions::new(), options).await,
+        Err(ResubmitError::AttemptsExhausted { attempts: 2, last_id }) if last_id == [2]
+    ));
+
+    // Failed transactions are not resubmitted.
+    let mut failing = account(&[(Executed, 1101)]);
+    assert!(matches!(
+        failing.send_transaction_header_resubmit(
+            &header,
+            SendOptions::new(),
+            ResubmitOptions::default()
+        ).await,
+        Err(ResubmitError::Failed { id, status_code: 1101, .. }) if id == [1]
+    ));
+
+    let mut executed = account(&[(Executed, 0)]);
+    let options = ResubmitOptions {
+        target: Executed,
+        ..Default::default()
+    };
+    let result = executed
+        .send_transaction_header_resubmit(&header, SendOptions::new(), options)
+        .await
+        .unwrap();
+    assert_eq!((1, Executed), (result.attempts, result.result.status));
+
+    // Transactions that the access node does not know about are dropped and resubmitted.
+    let mut dropped = account(&[]);
+    dropped.client().inner_mut().not_found = 1;
+    let options = ResubmitOptions {
+        delay: Duration::from_millis(1),
+        ..Default::default()
+    };
+    let resubmitted = dropped
+        .send_transaction_header_resubmit(&header, SendOptions::new(), options)
+        .await
+        .unwrap();
+    assert_eq!((vec![2], 2), (resubmitted.id, resubmitted.attempts));
+
+    // Transactions with an unknown status could still be executed, they time out instead.
+    let mut unknown = account(&[(Unknown, 0); 1000]);
+    let options = ResubmitOptions {
+        delay: Duration::from_millis(1),
+        timeout: Duration::from_millis(20),
+        ..Default::default()
+    };
+    assert!(matches!(
+        unknown.send_transaction_header_resubmit(&header, SendOptions::new(), options).await,
+        Err(ResubmitError::TimedOut { id }) if id == [1]
+    ));
+}
@@ -4,6 +4,9 @@ pub struct MockSigner;
 
 pub struct MockHasher([u8; 32]);
 
+// Keeps the start of longer inputs, for tests that hash whole transactions.
+pub struct TruncatingHasher([u8; 32]);
+
 #[derive(PartialEq, Eq, Debug)]
 pub struct MockSig(pub [u8; 32]);
 
@@ -59,3 +62,21 @@ impl FlowHasher for MockHasher {
         self.0
     }
 }
+
+impl FlowHasher for TruncatingHasher {
+    type Algorithm = Sha3;
+
+    fn new() -> Self {
+        Self([0; 32])
+    }
+
+    fn update<B: AsRef<[u8]> + ?Sized>(&mut self, data: &B) {
+        let data = data.as_ref();
+        let len = data.len().min(32);
+        self.0[0..len].copy_from_slice(&data[0..len]);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        self.0
+    }
+}
@@ -1,4 +1,6 @@
 use futures_util::future::*;
+use otopr::decoding::{DecodableMessage, Deserializer};
+use otopr::encoding::{EncodableMessage, ProtobufSerializer};
 
 use super::immediate_fut;
 use crate::access::*;
@@ -63,6 +65,65 @@ fn acc_01() -> Account {
     }
 }
 
+pub const ACC02_SECP256K1_KEY: [u8; 32] = [0x11; 32];
+
+pub const ACC02_P256_KEY: [u8; 32] = [0x22; 32];
+
+pub const ACC02_FULL_WEIGHT_KEY: [u8; 32] = [0x33; 32];
+
+fn acc_02() -> Account {
+    let secp256k1 = secp256k1::SecretKey::from_slice(&ACC02_SECP256K1_KEY).unwrap();
+    let secp256k1 = FlowSigner::serialize_public_key(
+        &secp256k1::Secp256k1::signing_only(),
+        &secp256k1::Secp256k1::signing_only().to_public_key(&secp256k1),
+    );
+    let p256 = p256::ecdsa::SigningKey::from_slice(&ACC02_P256_KEY).unwrap();
+    let p256 = P256Signer.serialize_public_key(&P256Signer.to_public_key(&p256));
+    let full_weight = secp256k1::SecretKey::from_slice(&ACC02_FULL_WEIGHT_KEY).unwrap();
+    let full_weight = FlowSigner::serialize_public_key(
+        &secp256k1::Secp256k1::signing_only(),
+        &secp256k1::Secp256k1::signing_only().to_public_key(&full_weight),
+    );
+
+    Account {
+        address: [0x02].into(),
+        balance: 42,
+        code: [].into(),
+        keys: vec![
+            AccountKey {
+                index: 0,
+                public_key: secp256k1.into(),
+                sign_algo: Secp256k1::CODE,
+                hash_algo: Sha3::CODE,
+                weight: 500,
+                sequence_number: 7,
+                revoked: false,
+            },
+            AccountKey {
+                index: 1,
+                public_key: p256.into(),
+                sign_algo: P256::CODE,
+                hash_algo: Sha2::CODE,
+                weight: 500,
+                sequence_number: 7,
+                revoked: false,
+            },
+            AccountKey {
+                index: 2,
+                public_key: full_weight.into(),
+                sign_algo: Secp256k1::CODE,
+                hash_algo: Sha2::CODE,
+                weight: 1000,
+                sequence_number: 3,
+                revoked: false,
+            },
+        ]
+        .into(),
+        contracts: Default::default(),
+    }
+}
+
+#[derive(Clone, Copy)]
 pub struct MockClient;
 
 impl GrpcClient<GetAccountAtLatestBlockRequest<&[u8]>, AccountResponse> for MockClient {
@@ -76,6 +137,7 @@ impl GrpcClient<GetAccountAtLatestBlockRequest<&[u8]>, AccountResponse> for Mock
     > {
         let fut = match input.address {
             [0x01] => ok(AccountResponse { account: acc_01() }),
+            [0x02] => ok(AccountResponse { account: acc_02() }),
             _ => err("address not found"),
         };
 
@@ -83,6 +145,361 @@ impl GrpcClient<GetAccountAtLatestBlockRequest<&[u8]>, AccountResponse> for Mock
     }
 }
 
+impl GrpcClient<GetLatestBlockHeaderRequest, BlockHeaderResponse> for MockClient {
+    type Error = &'static str;
+
+    fn send<'a>(
+        &'a mut self,
+        _: GetLatestBlockHeaderRequest,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<BlockHeaderResponse, Self::Error>> + 'a>,
+    > {
+        Box::pin(ok(BlockHeaderResponse(BlockHeader {
+            id: [0x42; 32].into(),
+            height: 42,
+            ..Default::default()
+        })))
+    }
+}
+
+impl<
+        Script,
+        Arguments,
+        ReferenceBlockId,
+        ProposalKeyAddress,
+        Payer,
+        Authorizers,
+        Payload,
+        Envelope,
+    >
+    GrpcClient<
+        SendTransactionRequest<
+            Script,
+            Arguments,
+            ReferenceBlockId,
+            ProposalKeyAddress,
+            Payer,
+            Authorizers,
+            Payload,
+            Envelope,
+        >,
+        SendTransactionResponse,
+    > for MockClient
+{
+    type Error = &'static str;
+
+    fn send<'a>(
+        &'a mut self,
+        _: SendTransactionRequest<
+            Script,
+            Arguments,
+            ReferenceBlockId,
+            ProposalKeyAddress,
+            Payer,
+            Authorizers,
+            Payload,
+            Envelope,
+        >,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<SendTransactionResponse, Self::Error>> + 'a>,
+    > {
+        Box::pin(ok(SendTransactionResponse {
+            id: MOCK_TRANSACTION_ID.into(),
+        }))
+    }
+}
+
+pub const MOCK_TRANSACTION_ID: [u8; 32] = [0xab; 32];
+
+const ACCOUNT_CREATED_PAYLOAD: &str = r#"{
+    "type": "Event",
+    "value": {
+        "id": "flow.AccountCreated",
+        "fields": [
+            {
+                "name": "address",
+                "value": { "type": "Address", "value": "0x0000000000000002" }
+            }
+        ]
+    }
+}"#;
+
+impl GrpcClient<GetTransactionRequest<&[u8]>, TransactionResultResponse> for MockClient {
+    type Error = &'static str;
+
+    fn send<'a>(
+        &'a mut self,
+        input: GetTransactionRequest<&[u8]>,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<TransactionResultResponse, Self::Error>> + 'a>,
+    > {
+        let fut = if input.id == MOCK_TRANSACTION_ID {
+            ok(TransactionResultResponse {
+                status: crate::transaction::TransactionStatus::Sealed,
+                status_code: 0,
+                error_message: String::new(),
+                events: vec![Event {
+                    ty: "flow.AccountCreated".into(),
+                    transaction_id: MOCK_TRANSACTION_ID.into(),
+                    transaction_index: 0,
+                    event_index: 0,
+                    payload: ACCOUNT_CREATED_PAYLOAD.as_bytes().into(),
+                }]
+                .into(),
+                block_id: [0x42; 32].into(),
+            })
+        } else {
+            err("transaction not found")
+        };
+
+        Box::pin(fut)
+    }
+}
+
+/// A [`MockClient`] that keeps the transactions sent to it, decoded from their encoding.
+#[derive(Default)]
+pub struct RecordingClient {
+    pub sent: Vec<crate::transaction::TransactionD>,
+}
+
+impl GrpcClient<GetAccountAtLatestBlockRequest<&[u8]>, AccountResponse> for RecordingClient {
+    type Error = &'static str;
+
+    fn send<'a>(
+        &'a mut self,
+        input: GetAccountAtLatestBlockRequest<&[u8]>,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<AccountResponse, Self::Error>> + 'a>,
+    > {
+        let result = immediate_fut(MockClient.send(input));
+        Box::pin(ready(result))
+    }
+}
+
+impl GrpcClient<GetLatestBlockHeaderRequest, BlockHeaderResponse> for RecordingClient {
+    type Error = &'static str;
+
+    fn send<'a>(
+        &'a mut self,
+        input: GetLatestBlockHeaderRequest,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<BlockHeaderResponse, Self::Error>> + 'a>,
+    > {
+        let result = immediate_fut(MockClient.send(input));
+        Box::pin(ready(result))
+    }
+}
+
+impl<
+        Script,
+        Arguments,
+        ReferenceBlockId,
+        ProposalKeyAddress,
+        Payer,
+        Authorizers,
+        Payload,
+        Envelope,
+    >
+    GrpcClient<
+        SendTransactionRequest<
+            Script,
+            Arguments,
+            ReferenceBlockId,
+            ProposalKeyAddress,
+            Payer,
+            Authorizers,
+            Payload,
+            Envelope,
+        >,
+        SendTransactionResponse,
+    > for RecordingClient
+where
+    crate::transaction::TransactionE<
+        Script,
+        Arguments,
+        ReferenceBlockId,
+        ProposalKeyAddress,
+        Payer,
+        Authorizers,
+        Payload,
+        Envelope,
+    >: EncodableMessage,
+{
+    type Error = &'static str;
+
+    fn send<'a>(
+        &'a mut self,
+        input: SendTransactionRequest<
+            Script,
+            Arguments,
+            ReferenceBlockId,
+            ProposalKeyAddress,
+            Payer,
+            Authorizers,
+            Payload,
+            Envelope,
+        >,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<SendTransactionResponse, Self::Error>> + 'a>,
+    > {
+        let mut buf = Vec::with_capacity(input.transaction.encoded_size());
+        input
+            .transaction
+            .encode(&mut ProtobufSerializer::new(&mut buf));
+
+        let mut encoded = &buf[..];
+        let transaction = DecodableMessage::decode(&mut Deserializer::new(&mut encoded))
+            .expect("Failed to decode the transaction");
+        self.sent.push(transaction);
+
+        Box::pin(ok(SendTransactionResponse {
+            id: MOCK_TRANSACTION_ID.into(),
+        }))
+    }
+}
+
+impl GrpcClient<GetTransactionRequest<&[u8]>, TransactionResultResponse> for RecordingClient {
+    type Error = &'static str;
+
+    fn send<'a>(
+        &'a mut self,
+        input: GetTransactionRequest<&[u8]>,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<TransactionResultResponse, Self::Error>> + 'a>,
+    > {
+        let result = immediate_fut(MockClient.send(input));
+        Box::pin(ready(result))
+    }
+}
+
+/// A [`MockClient`] whose transactions expire or fail with the statuses specified, in order, and
+/// are sealed once they run out. Every transaction sent has a new ID.
+///
+/// The first `not_found` requests for a transaction are answered with a `NotFound` status.
+pub struct ScriptedClient {
+    pub statuses: Vec<(crate::transaction::TransactionStatus, u32)>,
+    pub not_found: usize,
+    pub sent: u8,
+}
+
+impl ScriptedClient {
+    pub fn new(statuses: &[(crate::transaction::TransactionStatus, u32)]) -> Self {
+        Self {
+            statuses: statuses.iter().rev().copied().collect(),
+            not_found: 0,
+            sent: 0,
+        }
+    }
+}
+
+impl GrpcClient<GetAccountAtLatestBlockRequest<&[u8]>, AccountResponse> for ScriptedClient {
+    type Error = &'static str;
+
+    fn send<'a>(
+        &'a mut self,
+        input: GetAccountAtLatestBlockRequest<&[u8]>,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<AccountResponse, Self::Error>> + 'a>,
+    > {
+        let result = immediate_fut(MockClient.send(input));
+        Box::pin(ready(result))
+    }
+}
+
+impl GrpcClient<GetLatestBlockHeaderRequest, BlockHeaderResponse> for ScriptedClient {
+    type Error = &'static str;
+
+    fn send<'a>(
+        &'a mut self,
+        input: GetLatestBlockHeaderRequest,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<BlockHeaderResponse, Self::Error>> + 'a>,
+    > {
+        let result = immediate_fut(MockClient.send(input));
+        Box::pin(ready(result))
+    }
+}
+
+impl<
+        Script,
+        Arguments,
+        ReferenceBlockId,
+        ProposalKeyAddress,
+        Payer,
+        Authorizers,
+        Payload,
+        Envelope,
+    >
+    GrpcClient<
+        SendTransactionRequest<
+            Script,
+            Arguments,
+            ReferenceBlockId,
+            ProposalKeyAddress,
+            Payer,
+            Authorizers,
+            Payload,
+            Envelope,
+        >,
+        SendTransactionResponse,
+    > for ScriptedClient
+{
+    type Error = &'static str;
+
+    fn send<'a>(
+        &'a mut self,
+        _: SendTransactionRequest<
+            Script,
+            Arguments,
+            ReferenceBlockId,
+            ProposalKeyAddress,
+            Payer,
+            Authorizers,
+            Payload,
+            Envelope,
+        >,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<SendTransactionResponse, Self::Error>> + 'a>,
+    > {
+        self.sent += 1;
+        Box::pin(ok(SendTransactionResponse {
+            id: vec![self.sent],
+        }))
+    }
+}
+
+impl GrpcClient<GetTransactionRequest<&[u8]>, TransactionResultResponse> for ScriptedClient {
+    type Error = tonic::Status;
+
+    fn send<'a>(
+        &'a mut self,
+        _: GetTransactionRequest<&[u8]>,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<TransactionResultResponse, Self::Error>> + 'a>,
+    > {
+        if self.not_found > 0 {
+            self.not_found -= 1;
+            return Box::pin(err(tonic::Status::not_found("transaction not found")));
+        }
+
+        let (status, status_code) = self
+            .statuses
+            .pop()
+            .unwrap_or((crate::transaction::TransactionStatus::Sealed, 0));
+
+        Box::pin(ok(TransactionResultResponse {
+            status,
+            status_code,
+            error_message: match status_code {
+                0 => String::new(),
+                1007 => "[Error Code: 1007] invalid proposal key".into(),
+                _ => "[Error Code: 1101] cadence runtime error".into(),
+            },
+            ..Default::default()
+        }))
+    }
+}
+
 #[test]
 fn test_get_account() {
     let mut client = FlowClient::new(MockClient);
@@ -91,7 +508,11 @@ fn test_get_account() {
 
     assert_eq!(Ok(acc_01()), account);
 
-    let unknown = immediate_fut(client.account_at_latest_block(&[0x02]));
+    let account = immediate_fut(client.account_at_latest_block(&[0x02]));
+
+    assert_eq!(Ok(acc_02()), account);
+
+    let unknown = immediate_fut(client.account_at_latest_block(&[0xff]));
 
     assert_eq!(Err("address not found"), unknown);
 }
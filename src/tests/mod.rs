@@ -1,4 +1,29 @@
+pub mod algorithms;
 pub mod building;
+pub mod cadence_error;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "any-algorithms")]
+pub mod fcl;
 pub mod fixtures;
+#[cfg(feature = "hd-wallet")]
+pub mod hd;
+#[cfg(feature = "any-algorithms")]
+pub mod interchange;
+#[cfg(feature = "keystore")]
+pub mod keystore;
+pub mod limits;
 pub mod mock;
+#[cfg(feature = "payer-service")]
+pub mod payer;
+#[cfg(feature = "any-algorithms")]
+pub mod pipeline;
+pub mod proposer;
+#[cfg(all(feature = "remote-sign", feature = "any-algorithms"))]
+pub mod remote;
+#[cfg(feature = "any-algorithms")]
+pub mod session;
 pub mod signing;
+pub mod status;
+#[cfg(feature = "any-algorithms")]
+pub mod validate;
@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use super::mock::algorithms::*;
+use super::mock::client::*;
+use super::mock::immediate_fut;
+use crate::account::{Account, AnyAccount};
+use crate::algorithms::*;
+use crate::client::FlowClient;
+use crate::error::SigningSessionError;
+use crate::multi::interchange::PortableParty;
+use crate::multi::{Party, PartyBuilder, PartySigner, SigningParty};
+use crate::payer::{script_hash, Error, PayerPolicy, PayerService};
+
+type MockAccount = Account<MockClient, MockKey, MockSigner, TruncatingHasher>;
+
+const SCRIPT: &str = "transaction { prepare(signer: AuthAccount) {} }";
+
+fn party(script: &str, gas_limit: u64) -> SigningParty {
+    PartyBuilder::new()
+        .script(script)
+        .reference_block([0x42; 32])
+        .gas_limit(gas_limit)
+        .proposer_address([0x02])
+        .proposal_key_id(2)
+        .proposal_key_sequence_number(3)
+        .payer([0x01])
+        .authorizer([0x02])
+        .build()
+}
+
+#[test]
+fn test_payer_service() {
+    let payer = immediate_fut(MockAccount::new(MockClient, &[0x01], ACC01_KEY))
+        .expect("Failed to create MockAccount");
+
+    let secret_key =
+        AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY).unwrap();
+    let user = immediate_fut(AnyAccount::new(MockClient, &[0x02], secret_key))
+        .expect("Failed to create AnyAccount");
+
+    let policy = PayerPolicy::new()
+        .allow_script(SCRIPT)
+        .max_gas_limit(1000)
+        .rate_limit(2, Duration::from_secs(3600));
+    assert!(!policy.is_script_allowed("transaction {}"));
+    let service = PayerService::new(payer, policy);
+    let mut client = FlowClient::new(MockClient);
+
+    // The payload has to be signed first.
+    let mut unsigned = party(SCRIPT, 1000);
+    assert!(matches!(
+        immediate_fut(service.check(&mut client, &unsigned)),
+        Err(Error::MissingSignature(address)) if *address == [0x02]
+    ));
+
+    immediate_fut(user.sign_payload(&mut unsigned)).unwrap();
+    assert!(immediate_fut(service.check(&mut client, &unsigned)).is_ok());
+
+    // Payload signatures are verified with the keys of the account.
+    let mut forged = party(SCRIPT, 1000);
+    Party::<AnyHasher>::add_payload_signature(&mut forged, [0x02].into(), 2, [0x42; 64]);
+    assert!(matches!(
+        immediate_fut(service.check(&mut client, &forged)),
+        Err(Error::Session(SigningSessionError::InvalidSignature {
+            key_id: 2,
+            ..
+        }))
+    ));
+
+    let mut not_allowed = party("transaction {}", 1000);
+    immediate_fut(user.sign_payload(&mut not_allowed)).unwrap();
+    assert!(matches!(
+        immediate_fut(service.check(&mut client, &not_allowed)),
+        Err(Error::ScriptNotAllowed(hash)) if hash == script_hash("transaction {}")
+    ));
+
+    let mut too_much_gas = party(SCRIPT, 1001);
+    immediate_fut(user.sign_payload(&mut too_much_gas)).unwrap();
+    assert!(matches!(
+        immediate_fut(service.check(&mut client, &too_much_gas)),
+        Err(Error::GasLimitTooHigh {
+            gas_limit: 1001,
+            max: 1000
+        })
+    ));
+
+    // The service signs the envelope through the JSON format. An account proposing and
+    // authorizing more than once counts once towards the rate limit.
+    let mut repeated = PartyBuilder::new()
+        .script(SCRIPT)
+        .reference_block([0x42; 32])
+        .proposer_address([0x02])
+        .proposal_key_id(2)
+        .proposal_key_sequence_number(3)
+        .payer([0x01])
+        .authorizer([0x02])
+        .authorizer([0x01])
+        .authorizer([0x02])
+        .build();
+    immediate_fut(user.sign_payload(&mut repeated)).unwrap();
+    let signed =
+        immediate_fut(service.sign_json(&mut client, &PortableParty::new(repeated).export_json()))
+            .unwrap();
+    let signed = PortableParty::import_json(&signed).unwrap();
+    assert_eq!(1, signed.envelope_signatures().len());
+    assert_eq!(&[0x01][..], &*signed.envelope_signatures()[0].address);
+
+    // Rejected transactions do not count towards the rate limit.
+    assert!(
+        immediate_fut(service.sign(&mut client, &mut PortableParty::new(too_much_gas))).is_err()
+    );
+    assert!(immediate_fut(service.sign(&mut client, &mut PortableParty::new(forged))).is_err());
+    assert!(
+        immediate_fut(service.sign(&mut client, &mut PortableParty::new(unsigned.clone()))).is_ok()
+    );
+    assert!(matches!(
+        immediate_fut(service.sign(&mut client, &mut PortableParty::new(unsigned))),
+        Err(Error::RateLimited(address)) if *address == [0x02]
+    ));
+
+    let mut other_payer = PartyBuilder::new()
+        .script(SCRIPT)
+        .reference_block([0x42; 32])
+        .proposer_address([0x02])
+        .proposal_key_id(2)
+        .proposal_key_sequence_number(3)
+        .payer([0x04])
+        .authorizer([0x02])
+        .build();
+    immediate_fut(user.sign_payload(&mut other_payer)).unwrap();
+    assert!(matches!(
+        immediate_fut(service.check(&mut client, &other_payer)),
+        Err(Error::WrongPayer)
+    ));
+
+    assert!(matches!(
+        immediate_fut(service.sign_json(&mut client, "{")),
+        Err(Error::Interchange(_))
+    ));
+}
+
+#[test]
+fn test_payer_rate_limit_without_authorizers() {
+    let payer = immediate_fut(MockAccount::new(MockClient, &[0x01], ACC01_KEY))
+        .expect("Failed to create MockAccount");
+
+    let secret_key =
+        AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY).unwrap();
+    let user = immediate_fut(AnyAccount::new(MockClient, &[0x02], secret_key))
+        .expect("Failed to create AnyAccount");
+
+    let policy = PayerPolicy::new()
+        .allow_script("transaction {}")
+        .rate_limit(2, Duration::from_secs(3600));
+    let service = PayerService::new(payer, policy);
+    let mut client = FlowClient::new(MockClient);
+
+    // Transactions without authorizers count towards the rate limit of their proposer.
+    let mut party = PartyBuilder::new()
+        .script("transaction {}")
+        .reference_block([0x42; 32])
+        .proposer_address([0x02])
+        .proposal_key_id(2)
+        .proposal_key_sequence_number(3)
+        .payer([0x01])
+        .build();
+    immediate_fut(user.sign_payload(&mut party)).unwrap();
+
+    for _ in 0..2 {
+        assert!(
+            immediate_fut(service.sign(&mut client, &mut PortableParty::new(party.clone())))
+                .is_ok()
+        );
+    }
+    assert!(matches!(
+        immediate_fut(service.sign(&mut client, &mut PortableParty::new(party))),
+        Err(Error::RateLimited(address)) if *address == [0x02]
+    ));
+}
@@ -0,0 +1,83 @@
+use futures_util::stream::{self, StreamExt};
+
+use super::mock::client::*;
+use super::mock::immediate_fut;
+use crate::account::AnyAccount;
+use crate::algorithms::*;
+use crate::error::{PipelineError, ProposerPoolError};
+use crate::pipeline::Pipeline;
+use crate::proposer::ProposerPool;
+use crate::transaction::{TransactionHeaderBuilder, TransactionStatus};
+
+#[test]
+fn test_pipeline() {
+    let secret_key =
+        AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY).unwrap();
+    let mut account = immediate_fut(AnyAccount::new(MockClient, &[0x02], secret_key))
+        .expect("Failed to create AnyAccount");
+
+    let headers = || {
+        stream::iter(
+            (0..3)
+                .map(|_| {
+                    TransactionHeaderBuilder::new()
+                        .script_static("transaction {}")
+                        .build()
+                })
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    // Every key of the pool is used, at most 2 transactions are in flight.
+    let pool = ProposerPool::new([0x02], [0, 2]);
+    let mut outcomes = immediate_fut(
+        Pipeline::new(&mut account)
+            .proposer_pool(&pool)
+            .concurrency(4)
+            .submit(headers())
+            .collect::<Vec<_>>(),
+    );
+    outcomes.sort_by_key(|outcome| outcome.index);
+    assert_eq!(3, outcomes.len());
+    for (index, outcome) in outcomes.iter().enumerate() {
+        assert_eq!(index, outcome.index);
+        assert_eq!(Some(&MOCK_TRANSACTION_ID[..]), outcome.id.as_deref());
+        let result = outcome.result.as_ref().unwrap();
+        assert_eq!(TransactionStatus::Sealed, result.status);
+        assert_eq!(1, result.events.len());
+    }
+
+    // The sequence numbers of the keys were 7 and 3.
+    let key0 = pool.try_acquire().unwrap();
+    let key2 = pool.try_acquire().unwrap();
+    assert_eq!(13, key0.sequence_number() + key2.sequence_number());
+    key0.release_unused();
+    key2.release_unused();
+
+    // Without a pool, the primary key proposes every transaction.
+    let outcomes = immediate_fut(
+        Pipeline::new(&mut account)
+            .submit(headers())
+            .collect::<Vec<_>>(),
+    );
+    assert!(outcomes.iter().all(|outcome| outcome.result.is_ok()));
+    assert_eq!(Some(6), account.tracked_sequence_number());
+
+    // If no key can be acquired, the pipeline stops and leaves the remaining transactions in the
+    // stream.
+    let pool = ProposerPool::new([0x02], [5]);
+    let mut remaining = headers();
+    let outcomes = immediate_fut(
+        Pipeline::new(&mut account)
+            .proposer_pool(&pool)
+            .submit(&mut remaining)
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(1, outcomes.len());
+    assert_eq!((0, None), (outcomes[0].index, outcomes[0].id.as_deref()));
+    assert!(matches!(
+        outcomes[0].result,
+        Err(PipelineError::Proposer(ProposerPoolError::KeyNotFound(5)))
+    ));
+    assert_eq!(2, immediate_fut(remaining.count()));
+}
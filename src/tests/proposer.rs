@@ -0,0 +1,80 @@
+use super::mock::client::MockClient;
+use super::mock::immediate_fut;
+use crate::access::TransactionResultResponse;
+use crate::client::FlowClient;
+use crate::error::ProposerPoolError;
+use crate::proposer::*;
+use crate::transaction::TransactionStatus;
+
+fn result(status: TransactionStatus, error_message: &str) -> TransactionResultResponse {
+    TransactionResultResponse {
+        status,
+        status_code: !error_message.is_empty() as u32,
+        error_message: error_message.into(),
+        events: Default::default(),
+        block_id: [].into(),
+    }
+}
+
+#[test]
+fn proposer_pool() {
+    let mut client = FlowClient::new(MockClient);
+    let pool = ProposerPool::new([0x02], [0, 2]);
+
+    assert!(pool.try_acquire().is_none());
+
+    let key0 = immediate_fut(pool.acquire(&mut client)).unwrap().unwrap();
+    let key2 = immediate_fut(pool.acquire(&mut client)).unwrap().unwrap();
+    assert_eq!((key0.key_id(), key0.sequence_number()), (0, 7));
+    assert_eq!((key2.key_id(), key2.sequence_number()), (2, 3));
+    assert!(immediate_fut(pool.acquire(&mut client)).unwrap().is_none());
+
+    // Sealed transactions increment the sequence number, even if they failed.
+    key0.release(&result(TransactionStatus::Sealed, ""));
+    let key0 = pool.try_acquire().unwrap();
+    assert_eq!(key0.sequence_number(), 8);
+    key0.release(&result(
+        TransactionStatus::Sealed,
+        "[Error Code: 1101] cadence runtime error",
+    ));
+    let key0 = pool.try_acquire().unwrap();
+    assert_eq!(key0.sequence_number(), 9);
+
+    // Expired transactions do not.
+    key0.release(&result(TransactionStatus::Expired, ""));
+    let key0 = pool.try_acquire().unwrap();
+    assert_eq!(key0.sequence_number(), 9);
+
+    // Sequence number mismatches resync the key.
+    key0.release(&result(
+        TransactionStatus::Sealed,
+        "[Error Code: 1007] invalid proposal key: sequence number mismatch",
+    ));
+    assert_eq!(pool.available(), 0);
+    let key0 = immediate_fut(pool.acquire(&mut client)).unwrap().unwrap();
+    assert_eq!(key0.sequence_number(), 7);
+
+    // Finalizing returns the key.
+    let finalized = immediate_fut(key2.finalize(futures_util::future::ready(Ok::<_, ()>(Some(
+        result(TransactionStatus::Sealed, ""),
+    )))));
+    assert!(finalized.is_ok());
+    let key2 = pool.try_acquire().unwrap();
+    assert_eq!(key2.sequence_number(), 4);
+
+    // Dropped keys are resynced.
+    drop(key2);
+    key0.release_unused();
+    let key0 = pool.try_acquire().unwrap();
+    assert_eq!(key0.sequence_number(), 7);
+    let key2 = immediate_fut(pool.acquire(&mut client)).unwrap().unwrap();
+    assert_eq!(key2.sequence_number(), 3);
+    key0.release_unused();
+    key2.release_unused();
+
+    let pool = ProposerPool::new([0x02], [5]);
+    assert!(matches!(
+        immediate_fut(pool.acquire(&mut client)),
+        Err(ProposerPoolError::KeyNotFound(5))
+    ));
+}
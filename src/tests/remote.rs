@@ -0,0 +1,171 @@
+use std::convert::Infallible;
+use std::error::Error;
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server, StatusCode};
+use serde_json::{json, Value};
+
+use super::mock::algorithms::*;
+use super::mock::client::*;
+use super::mock::immediate_fut;
+use crate::account::*;
+use crate::algorithms::*;
+use crate::transaction::TransactionHeaderBuilder;
+
+type RemoteAccount = Account<MockClient, RemoteKey, RemoteSigner, AnyHasher>;
+
+fn local_key(key_id: &str) -> Option<AnySecretKey> {
+    match key_id {
+        "secp256k1" => AnySecretKey::new(Secp256k1::CODE, Sha3::CODE, &ACC02_SECP256K1_KEY).ok(),
+        "p256" => AnySecretKey::new(P256::CODE, Sha2::CODE, &ACC02_P256_KEY).ok(),
+        _ => None,
+    }
+}
+
+async fn signing_service(request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let path = request.uri().path().to_owned();
+    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+
+    let key = match body["key_id"].as_str().and_then(local_key) {
+        Some(key) => key,
+        None => {
+            let mut response = Response::new(Body::from("unknown key"));
+            *response.status_mut() = StatusCode::NOT_FOUND;
+            return Ok(response);
+        }
+    };
+
+    let signer = AnySigner::new();
+
+    let response = match &*path {
+        "/public-key" => json!({
+            "public_key": hex::encode(signer.serialize_public_key(&signer.to_public_key(&key))),
+            "sign_algo": key.sign_algo(),
+            "hash_algo": key.hash_algo().code(),
+        }),
+        "/sign" => {
+            let mut digest = [0; 32];
+            hex::decode_to_slice(body["digest"].as_str().unwrap(), &mut digest).unwrap();
+            json!({ "signature": hex::encode(signer.sign_populated(digest, &key).serialize()) })
+        }
+        _ => panic!("unexpected path {}", path),
+    };
+
+    Ok(Response::new(Body::from(response.to_string())))
+}
+
+/// Serves the signing service in the background, returning its endpoint.
+fn spawn_signing_service() -> String {
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service_fn(|_| async {
+        Ok::<_, Infallible>(service_fn(signing_service))
+    }));
+    let endpoint = format!("http://{}", server.local_addr());
+    tokio::spawn(server);
+    endpoint
+}
+
+#[tokio::test]
+async fn remote_signing() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let endpoint = spawn_signing_service();
+
+    let signer = RemoteSigner::new();
+    let secp256k1 = signer.fetch_key(&*endpoint, "secp256k1").await?;
+    let p256 = signer.fetch_key(&*endpoint, "p256").await?;
+
+    assert_eq!(secp256k1.sign_algo(), Secp256k1::CODE);
+    assert_eq!(p256.hash_algo(), Sha2::CODE);
+
+    let account = RemoteAccount::new_multisign(MockClient, &[0x02], 0, &[secp256k1, p256]).await?;
+
+    let data = b"Sign me remotely!";
+    let signatures: Vec<_> = account
+        .sign_data_async(data)
+        .await?
+        .iter()
+        .map(Signature::serialize)
+        .collect();
+
+    let local = immediate_fut(AnyAccount::new_multisign(
+        MockClient,
+        &[0x02],
+        0,
+        &[local_key("secp256k1").unwrap(), local_key("p256").unwrap()],
+    ))?;
+    let expected: Vec<_> = local.sign_data(data).map(|sig| sig.serialize()).collect();
+
+    assert_eq!(signatures, expected);
+
+    let unknown = signer.fetch_key(&*endpoint, "unknown").await;
+
+    assert!(matches!(
+        unknown,
+        Err(crate::error::RemoteSignerError::Status { status: 404, .. })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn remote_sending() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let endpoint = spawn_signing_service();
+
+    let signer = RemoteSigner::new();
+    let remote_keys = [
+        signer.fetch_key(&*endpoint, "secp256k1").await?,
+        signer.fetch_key(&*endpoint, "p256").await?,
+    ];
+    let local_keys = [local_key("secp256k1").unwrap(), local_key("p256").unwrap()];
+
+    let mut remote = Account::<RecordingClient, RemoteKey, RemoteSigner, AnyHasher>::new_multisign(
+        RecordingClient::default(),
+        &[0x02],
+        0,
+        &remote_keys,
+    )
+    .await?;
+    let mut local =
+        AnyAccount::new_multisign(RecordingClient::default(), &[0x02], 0, &local_keys).await?;
+
+    let header = TransactionHeaderBuilder::new()
+        .script_static("transaction {}")
+        .build();
+
+    remote.send_transaction_header(&header).await?;
+    local.send_transaction_header(&header).await?;
+
+    let sent = [
+        &remote.client().inner_mut().sent[0],
+        &local.client().inner_mut().sent[0],
+    ];
+    let signatures = sent.map(|transaction| {
+        transaction
+            .envelope_signatures
+            .iter()
+            .map(|signature| (signature.key_id, signature.signature.clone()))
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(signatures[0].len(), 2);
+    assert_eq!(signatures[0], signatures[1]);
+
+    // The remote account pays for a transaction proposed by another account.
+    let mut proposer = Account::<_, MockKey, MockSigner, TruncatingHasher>::new(
+        RecordingClient::default(),
+        &[0x01],
+        ACC01_KEY,
+    )
+    .await?;
+    proposer
+        .send_transaction_header_with(&header, SendOptions::new().payer(&remote))
+        .await?;
+
+    let transaction = &proposer.client().inner_mut().sent[0];
+    assert_eq!(*transaction.payer, [0x02]);
+    assert_eq!(transaction.envelope_signatures.len(), 2);
+    assert!(transaction
+        .envelope_signatures
+        .iter()
+        .all(|signature| *signature.address == [0x02]));
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use super::mock::client::*;
+use super::mock::immediate_fut;
+use crate::account::AnyAccount;
+use crate::algorithms::*;
+use crate::client::FlowClient;
+use crate::multi::session::{Error, Role, SessionState, SigningSession};
+use crate::multi::{PartyBuilder, PartySigner};
+use crate::sign::{One, SignMethod};
+
+#[test]
+fn test_signing_session() {
+    let secret_key =
+        AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY).unwrap();
+    let authorizer = immediate_fut(AnyAccount::new(MockClient, &[0x02], secret_key))
+        .expect("Failed to create AnyAccount");
+
+    // The payer at 0x03 has the same keys as 0x02.
+    let mut payer_account =
+        immediate_fut(FlowClient::new(MockClient).account_at_latest_block(&[0x02])).unwrap();
+    payer_account.address = [0x03].into();
+    let payer = unsafe {
+        AnyAccount::new_unchecked(
+            MockClient,
+            [0x03].into(),
+            SignMethod::One(One {
+                key_id: 2,
+                key: AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY)
+                    .unwrap(),
+            }),
+        )
+    };
+
+    let party = PartyBuilder::new()
+        .script("transaction { prepare(signer: AuthAccount) {} }")
+        .reference_block([0x42; 32])
+        .proposer_address([0x02])
+        .proposal_key_id(2)
+        .proposal_key_sequence_number(3)
+        .payer([0x03])
+        .authorizer([0x02])
+        .build();
+
+    assert!(matches!(
+        SigningSession::with_accounts(party.clone(), []),
+        Err(Error::MissingAccount)
+    ));
+
+    let authorizer_account =
+        immediate_fut(FlowClient::new(MockClient).account_at_latest_block(&[0x02])).unwrap();
+    let mut session =
+        SigningSession::with_accounts(party, [authorizer_account, payer_account]).unwrap();
+
+    assert_eq!(SessionState::Payload, session.state());
+    assert_eq!(
+        vec![
+            (&[0x02][..], Role::Proposer),
+            (&[0x02][..], Role::Authorizer),
+            (&[0x03][..], Role::Payer)
+        ],
+        session.pending_signers()
+    );
+
+    // The payer cannot sign before the payload is signed, nor sign the payload.
+    assert!(matches!(
+        immediate_fut(session.sign(&payer)),
+        Err(Error::PayloadIncomplete)
+    ));
+    let mut signature = immediate_fut(payer.sign_envelope(session.party()))
+        .unwrap()
+        .remove(0);
+    assert!(matches!(
+        session.add_payload_signature(signature.clone()),
+        Err(Error::WrongRole)
+    ));
+
+    // Signatures are verified.
+    signature.address = [0x02].into();
+    assert!(matches!(
+        session.add_payload_signature(signature),
+        Err(Error::InvalidSignature { key_id: 2, .. })
+    ));
+
+    immediate_fut(session.sign(&authorizer)).unwrap();
+    assert_eq!(SessionState::Envelope, session.state());
+    assert!(matches!(
+        immediate_fut(session.sign(&authorizer)),
+        Err(Error::DuplicateKey(2))
+    ));
+
+    assert!(matches!(
+        session.clone().into_transaction(),
+        Err(Error::Incomplete)
+    ));
+    immediate_fut(session.sign(&payer)).unwrap();
+    assert_eq!(SessionState::Complete, session.state());
+    assert!(session.pending_signers().is_empty());
+
+    // Payload signatures cannot be added after the envelope is signed.
+    assert!(matches!(
+        immediate_fut(session.sign(&authorizer)),
+        Err(Error::EnvelopeSigned)
+    ));
+
+    let transaction = session.into_transaction().unwrap();
+    assert_eq!(1, transaction.payload_signatures.len());
+    assert_eq!(1, transaction.envelope_signatures.len());
+}
@@ -0,0 +1,143 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures_util::future::ready;
+use futures_util::StreamExt;
+
+use crate::access::*;
+use crate::client::GrpcClient;
+use crate::error::TransactionStatusError;
+use crate::transaction::{Finalize, StatusStream, TransactionStatus};
+
+/// Answers with the statuses specified, one per request.
+struct StatusClient(VecDeque<(TransactionStatus, u32)>);
+
+impl GrpcClient<GetTransactionRequest<&[u8]>, TransactionResultResponse> for StatusClient {
+    type Error = &'static str;
+
+    fn send<'a>(
+        &'a mut self,
+        _: GetTransactionRequest<&[u8]>,
+    ) -> Pin<Box<dyn Future<Output = Result<TransactionResultResponse, Self::Error>> + 'a>> {
+        let result = match self.0.pop_front() {
+            Some((status, status_code)) => Ok(TransactionResultResponse {
+                status,
+                status_code,
+                error_message: if status_code == 0 {
+                    String::new()
+                } else {
+                    "[Error Code: 1101] cadence runtime error".into()
+                },
+                ..Default::default()
+            }),
+            None => Err("no more statuses"),
+        };
+
+        Box::pin(ready(result))
+    }
+}
+
+fn stream(statuses: &[(TransactionStatus, u32)]) -> StatusStream<'static, StatusClient> {
+    StatusStream::new(&[0x42], StatusClient(statuses.iter().copied().collect()))
+        .delay(Duration::from_millis(1))
+}
+
+#[tokio::test]
+async fn test_status_stream() {
+    use TransactionStatus::*;
+
+    // Only transitions are yielded, and the stream ends once sealed.
+    let statuses: Vec<_> = stream(&[
+        (Unknown, 0),
+        (Pending, 0),
+        (Pending, 0),
+        (Finalized, 0),
+        (Executed, 0),
+        (Sealed, 0),
+    ])
+    .map(Result::unwrap)
+    .collect()
+    .await;
+    assert_eq!(
+        vec![Unknown, Pending, Finalized, Executed, Sealed],
+        statuses
+    );
+
+    let executed = stream(&[(Pending, 0), (Executed, 0), (Sealed, 0)])
+        .wait_for(Executed)
+        .await
+        .unwrap();
+    assert_eq!(Executed, executed.status);
+
+    // Statuses that are skipped still satisfy the target.
+    let sealed = stream(&[(Pending, 0), (Sealed, 0)])
+        .wait_for(Finalized)
+        .await
+        .unwrap();
+    assert_eq!(Sealed, sealed.status);
+
+    assert!(matches!(
+        stream(&[(Pending, 0), (Expired, 0)]).wait_for(Sealed).await,
+        Err(TransactionStatusError::Expired)
+    ));
+
+    match stream(&[(Pending, 0), (Executed, 1101)])
+        .wait_for(Sealed)
+        .await
+    {
+        Err(TransactionStatusError::Failed {
+            status_code,
+            error_message,
+        }) => {
+            assert_eq!(1101, status_code);
+            assert!(error_message.contains("1101"));
+        }
+        other => panic!("unexpected result: {:?}", other.map(|r| r.status)),
+    }
+
+    assert!(matches!(
+        stream(&[(Pending, 0)]).wait_for(Sealed).await,
+        Err(TransactionStatusError::Custom(_))
+    ));
+
+    let mut pending = stream(&[(Pending, 0); 1000]).timeout(Duration::from_millis(20));
+    assert_eq!(Some(Pending), pending.next().await.map(Result::unwrap));
+    assert!(matches!(
+        pending.next().await,
+        Some(Err(TransactionStatusError::Timeout))
+    ));
+    assert_eq!(Pending, pending.last_response().unwrap().status);
+    assert!(pending.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_finalize() {
+    use TransactionStatus::*;
+
+    fn finalize(statuses: &[(TransactionStatus, u32)]) -> Finalize<'static, StatusClient> {
+        Finalize::new(
+            &[0x42],
+            StatusClient(statuses.iter().copied().collect()),
+            Duration::from_millis(1),
+            Duration::from_millis(20),
+        )
+    }
+
+    let sealed = finalize(&[(Pending, 0), (Executed, 0), (Sealed, 0)]).await;
+    assert_eq!(Sealed, sealed.unwrap().unwrap().status);
+
+    let expired = finalize(&[(Pending, 0), (Expired, 0)]).await;
+    assert_eq!(Expired, expired.unwrap().unwrap().status);
+
+    // Failed transactions are finalized with their result.
+    let failed = finalize(&[(Pending, 0), (Executed, 1101)]).await;
+    assert_eq!(1101, failed.unwrap().unwrap().status_code);
+
+    assert!(finalize(&[(Pending, 0); 1000]).await.unwrap().is_none());
+    assert!(matches!(
+        finalize(&[(Pending, 0)]).await,
+        Err(TransactionStatusError::Custom(_))
+    ));
+}
@@ -0,0 +1,64 @@
+use super::mock::client::*;
+use super::mock::immediate_fut;
+use crate::account::AnyAccount;
+use crate::algorithms::*;
+use crate::client::FlowClient;
+use crate::multi::interchange::PortableParty;
+use crate::multi::{PartyBuilder, SigningParty};
+use crate::transaction::{ProblemKind, SignerProblem};
+
+fn party(sequence_number: u64) -> SigningParty {
+    PartyBuilder::new()
+        .script("transaction { prepare(signer: AuthAccount) {} }")
+        .reference_block([0x42; 32])
+        .proposer_address([0x02])
+        .proposal_key_id(2)
+        .proposal_key_sequence_number(sequence_number)
+        .payer([0x02])
+        .authorizer([0x02])
+        .build()
+}
+
+fn problem(kind: ProblemKind) -> SignerProblem {
+    SignerProblem {
+        address: [0x02].into(),
+        kind,
+    }
+}
+
+#[test]
+fn test_validate() {
+    let secret_key =
+        AnySecretKey::new(Secp256k1::CODE, Sha2::CODE, &ACC02_FULL_WEIGHT_KEY).unwrap();
+    let account = immediate_fut(AnyAccount::new(MockClient, &[0x02], secret_key))
+        .expect("Failed to create AnyAccount");
+    let mut client = FlowClient::new(MockClient);
+
+    let transaction = account.sign_party_as_payer(party(3));
+    let problems = immediate_fut(transaction.validate(&mut client)).unwrap();
+    assert_eq!(Vec::<SignerProblem>::new(), problems);
+
+    let transaction = account.sign_party_as_payer(party(4));
+    let problems = immediate_fut(transaction.validate(&mut client)).unwrap();
+    assert_eq!(
+        vec![problem(ProblemKind::SequenceNumberMismatch {
+            on_chain: 3,
+            proposed: 4
+        })],
+        problems
+    );
+
+    // The payer does not sign the payload.
+    let mut party = party(3);
+    account.sign_party(&mut party);
+    let transaction = PortableParty::new(party).into_transaction();
+    let problems = immediate_fut(transaction.validate(&mut client)).unwrap();
+    assert_eq!(
+        vec![
+            problem(ProblemKind::UnexpectedSignature { key_id: 2 }),
+            problem(ProblemKind::MissingProposalKeySignature),
+            problem(ProblemKind::NotEnoughWeight(0)),
+        ],
+        problems
+    );
+}
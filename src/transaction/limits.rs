@@ -0,0 +1,203 @@
+use otopr::encoding::EncodableMessage;
+
+use super::{TransactionE, TransactionHeader};
+use crate::error::LimitError;
+use crate::multi::SigningParty;
+
+/// Limits that access nodes enforce on transactions, checked before sending them.
+///
+/// The default limits are the defaults of the access nodes of the Flow networks, see
+/// [`TransactionLimits::new`]. Transactions sent by an [`Account`] are checked against them, see
+/// [`Account::set_transaction_limits`].
+///
+/// [`Account`]: crate::account::Account
+/// [`Account::set_transaction_limits`]: crate::account::Account::set_transaction_limits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransactionLimits {
+    /// The maximum gas limit of a transaction.
+    pub max_gas_limit: u64,
+
+    /// The maximum size of the script, in bytes.
+    pub max_script_size: usize,
+
+    /// The maximum number of arguments.
+    ///
+    /// Access nodes do not limit the number of arguments, only their total size.
+    pub max_argument_count: usize,
+
+    /// The maximum size of each argument, in bytes.
+    pub max_argument_size: usize,
+
+    /// The maximum size of the transaction sent over the network, in bytes.
+    pub max_transaction_size: usize,
+}
+
+impl TransactionLimits {
+    /// Creates the limits of the Flow networks, which are the defaults of the access nodes in
+    /// `flow-go`:
+    ///
+    ///  - the gas limit is at most 9999, `DefaultMaxTransactionGasLimit`;
+    ///  - transactions are at most 1,500,000 bytes, `DefaultMaxTransactionByteSize`.
+    ///
+    /// Access nodes do not limit the script, the arguments or their number on their own, so the
+    /// script and each argument can take the whole transaction, and `max_argument_count` is
+    /// `usize::MAX`: only the size of the transaction bounds the number of arguments.
+    pub const fn new() -> Self {
+        Self {
+            max_gas_limit: 9999,
+            max_script_size: 1_500_000,
+            max_argument_count: usize::MAX,
+            max_argument_size: 1_500_000,
+            max_transaction_size: 1_500_000,
+        }
+    }
+
+    /// Checks the gas limit of a transaction.
+    pub fn check_gas_limit(&self, gas_limit: u64) -> Result<(), LimitError> {
+        if gas_limit > self.max_gas_limit {
+            return Err(LimitError::GasLimitTooHigh {
+                gas_limit,
+                max: self.max_gas_limit,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks the script and the arguments of a transaction header.
+    ///
+    /// The size of the transaction is estimated from the script and the arguments only, since the
+    /// rest of the transaction is not known yet.
+    pub fn check_header<Arguments, Argument>(
+        &self,
+        header: &TransactionHeader<Arguments>,
+    ) -> Result<(), LimitError>
+    where
+        Arguments: AsRef<[Argument]>,
+        Argument: AsRef<[u8]>,
+    {
+        let arguments = header.arguments.as_ref();
+        let size = header.script.len()
+            + arguments
+                .iter()
+                .map(|argument| argument.as_ref().len())
+                .sum::<usize>();
+
+        self.check_parts(
+            header.script.as_bytes(),
+            arguments.iter().map(AsRef::as_ref),
+            size,
+        )
+    }
+
+    /// Checks a party, such as one built by a [`PartyBuilder`].
+    ///
+    /// The size of the transaction counts the payload signatures collected so far, but not the
+    /// envelope signatures.
+    ///
+    /// [`PartyBuilder`]: crate::multi::PartyBuilder
+    pub fn check_party(&self, party: &SigningParty) -> Result<(), LimitError> {
+        let transaction = party.clone().into_transaction::<Box<[u8]>, [u8; 64]>([]);
+        self.check_transaction(&transaction)
+    }
+
+    /// Checks a transaction that is ready to be sent.
+    pub fn check_transaction<
+        Script,
+        Arguments,
+        Argument,
+        ReferenceBlockId,
+        ProposalKeyAddress,
+        Payer,
+        Authorizers,
+        PayloadSignatures,
+        EnvelopeSignatures,
+    >(
+        &self,
+        transaction: &TransactionE<
+            Script,
+            Arguments,
+            ReferenceBlockId,
+            ProposalKeyAddress,
+            Payer,
+            Authorizers,
+            PayloadSignatures,
+            EnvelopeSignatures,
+        >,
+    ) -> Result<(), LimitError>
+    where
+        TransactionE<
+            Script,
+            Arguments,
+            ReferenceBlockId,
+            ProposalKeyAddress,
+            Payer,
+            Authorizers,
+            PayloadSignatures,
+            EnvelopeSignatures,
+        >: EncodableMessage,
+        Script: AsRef<[u8]>,
+        for<'a> &'a Arguments: IntoIterator<Item = &'a Argument>,
+        Argument: AsRef<[u8]>,
+    {
+        self.check_gas_limit(transaction.gas_limit)?;
+        self.check_parts(
+            transaction.script.as_ref(),
+            (&transaction.arguments).into_iter().map(AsRef::as_ref),
+            transaction.encoded_size(),
+        )
+    }
+
+    fn check_parts<'a>(
+        &self,
+        script: &[u8],
+        arguments: impl Iterator<Item = &'a [u8]>,
+        size: usize,
+    ) -> Result<(), LimitError> {
+        if script.is_empty() {
+            return Err(LimitError::EmptyScript);
+        }
+
+        if script.len() > self.max_script_size {
+            return Err(LimitError::ScriptTooLarge {
+                size: script.len(),
+                max: self.max_script_size,
+            });
+        }
+
+        let mut count = 0;
+        for (index, argument) in arguments.enumerate() {
+            if argument.len() > self.max_argument_size {
+                return Err(LimitError::ArgumentTooLarge {
+                    index,
+                    size: argument.len(),
+                    max: self.max_argument_size,
+                });
+            }
+            count += 1;
+        }
+
+        if count > self.max_argument_count {
+            return Err(LimitError::TooManyArguments {
+                count,
+                max: self.max_argument_count,
+            });
+        }
+
+        if size > self.max_transaction_size {
+            return Err(LimitError::TransactionTooLarge {
+                size,
+                max: self.max_transaction_size,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for TransactionLimits {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
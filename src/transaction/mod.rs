@@ -20,6 +20,17 @@ pub use template::*;
 mod finalize;
 pub use finalize::*;
 
+mod limits;
+pub use limits::*;
+
+mod status;
+pub use status::*;
+
+#[cfg(feature = "any-algorithms")]
+mod validate;
+#[cfg(feature = "any-algorithms")]
+pub use validate::*;
+
 /// Status of a transaction.
 #[derive(Enumeration, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TransactionStatus {
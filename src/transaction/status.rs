@@ -0,0 +1,240 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_util::{Stream, StreamExt};
+
+use crate::access::*;
+use crate::client::GrpcClient;
+use crate::error::TransactionStatusError;
+use crate::transaction::*;
+
+type Request<'a, C, E> =
+    Pin<Box<dyn Future<Output = (C, Result<TransactionResultResponse, E>)> + 'a>>;
+
+/// Repeatedly queries a client about a transaction, yielding its status every time it changes.
+///
+/// The stream ends after the transaction is sealed or expired. If the transaction failed to
+/// execute, the timeout has been reached, or an error occured while making requests, an error is
+/// yielded and the stream ends.
+///
+/// The default delay is 2 seconds between requests, and the default timeout is 60 seconds.
+///
+/// ```rust,no_run
+/// # use flow_sdk::access::SendTransactionResponse;
+/// # use flow_sdk::client::TonicHyperFlowClient;
+/// # use flow_sdk::transaction::TransactionStatus;
+/// # async fn run(
+/// #     response: SendTransactionResponse,
+/// #     client: &mut TonicHyperFlowClient,
+/// # ) -> Result<(), flow_sdk::error::TransactionStatusError> {
+/// // Faster than waiting for the transaction to be sealed.
+/// let result = response
+///     .status_stream(client)
+///     .wait_for(TransactionStatus::Executed)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct StatusStream<'a, C>
+where
+    C: GrpcClient<GetTransactionRequest<&'a [u8]>, TransactionResultResponse>,
+{
+    tx_id: &'a [u8],
+    delay: Duration,
+    timeout: Duration,
+    deadline: Option<futures_timer::Delay>,
+    state: StatusState<'a, C, C::Error>,
+    last_response: Option<TransactionResultResponse>,
+}
+
+enum StatusState<'a, C, E> {
+    Waiting(Option<C>, futures_timer::Delay),
+    Request(Request<'a, C, E>),
+    Done,
+}
+
+// The client is never pinned, it is only moved in and out of the requests.
+impl<'a, C> Unpin for StatusStream<'a, C> where
+    C: GrpcClient<GetTransactionRequest<&'a [u8]>, TransactionResultResponse>
+{
+}
+
+impl<'a, C> StatusStream<'a, C>
+where
+    C: GrpcClient<GetTransactionRequest<&'a [u8]>, TransactionResultResponse> + 'a,
+    C::Error: 'a,
+{
+    /// Creates a stream of the statuses of the transaction with the id specified.
+    pub fn new(tx_id: &'a [u8], client: C) -> Self {
+        Self {
+            tx_id,
+            delay: Duration::from_secs(2),
+            timeout: Duration::from_secs(60),
+            deadline: None,
+            state: StatusState::Request(Self::request(tx_id, client)),
+            last_response: None,
+        }
+    }
+
+    /// Sets the delay between requests.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Sets the timeout, counted from the first time the stream is polled.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the last response received.
+    #[inline]
+    pub fn last_response(&self) -> Option<&TransactionResultResponse> {
+        self.last_response.as_ref()
+    }
+
+    /// Returns the last response received, consuming the stream.
+    #[inline]
+    pub fn into_last_response(self) -> Option<TransactionResultResponse> {
+        self.last_response
+    }
+
+    /// Waits until the transaction has reached the target status, and returns its result.
+    ///
+    /// Statuses are ordered from [`TransactionStatus::Pending`] to [`TransactionStatus::Sealed`].
+    /// Waiting for [`TransactionStatus::Executed`] confirms the transaction sooner than waiting
+    /// for it to be sealed.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the transaction failed to execute or expired before
+    /// reaching the target status, if the timeout has been reached, or if the client returns any
+    /// errors while making requests.
+    pub async fn wait_for(
+        mut self,
+        target: TransactionStatus,
+    ) -> Result<TransactionResultResponse, TransactionStatusError> {
+        while let Some(status) = self.next().await {
+            let status = status?;
+
+            if status == TransactionStatus::Expired && target != TransactionStatus::Expired {
+                return Err(TransactionStatusError::Expired);
+            }
+
+            if status.reaches(target) {
+                break;
+            }
+        }
+
+        self.last_response.ok_or(TransactionStatusError::Timeout)
+    }
+
+    fn request(tx_id: &'a [u8], mut client: C) -> Request<'a, C, C::Error> {
+        Box::pin(async move {
+            let result = client.send(GetTransactionRequest { id: tx_id }).await;
+            (client, result)
+        })
+    }
+}
+
+impl<'a, C> Stream for StatusStream<'a, C>
+where
+    C: GrpcClient<GetTransactionRequest<&'a [u8]>, TransactionResultResponse> + 'a,
+    C::Error: 'a,
+{
+    type Item = Result<TransactionStatus, TransactionStatusError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let timeout = this.timeout;
+        let deadline = this
+            .deadline
+            .get_or_insert_with(|| futures_timer::Delay::new(timeout));
+
+        loop {
+            match &mut this.state {
+                StatusState::Done => return Poll::Ready(None),
+                StatusState::Waiting(client, delay) => {
+                    if Pin::new(delay).poll(cx).is_pending() {
+                        break;
+                    }
+
+                    let client = client.take().expect("the client is only taken once");
+                    this.state = StatusState::Request(Self::request(this.tx_id, client));
+                }
+                StatusState::Request(request) => {
+                    let (client, result) = match request.as_mut().poll(cx) {
+                        Poll::Ready(ready) => ready,
+                        Poll::Pending => break,
+                    };
+
+                    let response = match result {
+                        Ok(response) => response,
+                        Err(error) => {
+                            this.state = StatusState::Done;
+                            return Poll::Ready(Some(Err(TransactionStatusError::Custom(
+                                error.into(),
+                            ))));
+                        }
+                    };
+
+                    let changed = !matches!(
+                        &this.last_response,
+                        Some(last) if last.status == response.status
+                    );
+                    let status = response.status;
+                    let failed = response.status_code != 0;
+                    let done = failed
+                        || matches!(
+                            status,
+                            TransactionStatus::Sealed | TransactionStatus::Expired
+                        );
+
+                    let error = failed.then(|| TransactionStatusError::Failed {
+                        status_code: response.status_code,
+                        error_message: response.error_message.clone(),
+                    });
+                    this.last_response = Some(response);
+
+                    this.state = if done {
+                        StatusState::Done
+                    } else {
+                        StatusState::Waiting(Some(client), futures_timer::Delay::new(this.delay))
+                    };
+
+                    if let Some(error) = error {
+                        return Poll::Ready(Some(Err(error)));
+                    }
+
+                    if changed {
+                        return Poll::Ready(Some(Ok(status)));
+                    }
+                }
+            }
+        }
+
+        // We haven't made any progress.
+        match Pin::new(deadline).poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(()) => {
+                this.state = StatusState::Done;
+                Poll::Ready(Some(Err(TransactionStatusError::Timeout)))
+            }
+        }
+    }
+}
+
+impl TransactionStatus {
+    /// Whether a transaction with this status has reached the target status, statuses being
+    /// ordered from pending to sealed.
+    pub(crate) fn reaches(self, target: TransactionStatus) -> bool {
+        let is_ordered = !matches!(
+            self,
+            TransactionStatus::Unknown | TransactionStatus::Expired
+        );
+        self == target || (is_ordered && self as u8 >= target as u8)
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::{HashMap, HashSet};
+
+use rlp::RlpStream;
+
+use super::rlp::{rlp_encode_transaction_envelope, rlp_encode_transaction_payload};
+use crate::access::{AccountResponse, GetAccountAtLatestBlockRequest};
+use crate::account::PADDED_TRANSACTION_DOMAIN_TAG;
+use crate::client::{FlowClient, GrpcClient};
+use crate::entities::AccountKey;
+use crate::error::{BoxError, VerifyError};
+use crate::message::FULL_WEIGHT;
+use crate::multi::{PartyTransaction, SigningParty};
+
+/// A problem with the signatures of an account, found by [`TransactionE::validate`].
+///
+/// [`TransactionE::validate`]: crate::transaction::TransactionE::validate
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignerProblem {
+    /// The address of the account.
+    pub address: Box<[u8]>,
+
+    /// What is wrong.
+    pub kind: ProblemKind,
+}
+
+/// The kinds of problems that [`TransactionE::validate`] can find.
+///
+/// [`TransactionE::validate`]: crate::transaction::TransactionE::validate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProblemKind {
+    /// The account signed in a role it does not have, such as an account that does not take part
+    /// in the transaction, or the payer signing the payload.
+    UnexpectedSignature {
+        /// The index of the key.
+        key_id: u32,
+    },
+
+    /// More than one signature was made by the key with this index.
+    DuplicateKey(u32),
+
+    /// The account does not have a key with this index.
+    KeyNotFound(u32),
+
+    /// A signature could not be verified.
+    InvalidSignature {
+        /// The index of the key.
+        key_id: u32,
+        /// Why the signature could not be verified.
+        error: VerifyError,
+    },
+
+    /// The keys of the account that signed have less than the full weight in total.
+    NotEnoughWeight(u32),
+
+    /// The proposer did not sign with the proposal key.
+    MissingProposalKeySignature,
+
+    /// The proposer does not have a key with the index of the proposal key.
+    ProposalKeyNotFound(u32),
+
+    /// The proposal key is revoked.
+    ProposalKeyRevoked,
+
+    /// The sequence number of the proposal key does not match the one on chain.
+    SequenceNumberMismatch {
+        /// The sequence number of the key on chain.
+        on_chain: u64,
+        /// The sequence number the transaction was proposed with.
+        proposed: u64,
+    },
+}
+
+impl<SigAddr, Sig> PartyTransaction<SigAddr, Sig>
+where
+    SigAddr: AsRef<[u8]>,
+    Sig: AsRef<[u8]>,
+{
+    /// Checks the transaction against the keys of its accounts on chain before sending it.
+    ///
+    /// Every signature is verified with the key it references, the proposer must have signed with
+    /// the proposal key, and the payer and each authorizer must have signed with full weight. The
+    /// sequence number of the proposal key must match the one on chain.
+    ///
+    /// Returns the problems found, which is empty if the transaction is valid.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the client returns any errors while making requests.
+    pub async fn validate<Client>(
+        &self,
+        client: &mut FlowClient<Client>,
+    ) -> Result<Vec<SignerProblem>, BoxError>
+    where
+        Client: for<'a> GrpcClient<GetAccountAtLatestBlockRequest<&'a [u8]>, AccountResponse>,
+    {
+        let proposer = &*self.proposal_key.address;
+        let payer = &*self.payer;
+
+        let signer_map = SigningParty::build_signer_map(proposer, payer, &self.authorizers);
+        let mut keys = HashMap::new();
+
+        for address in signer_map.keys() {
+            let account = client
+                .account_at_latest_block(address)
+                .await
+                .map_err(Into::into)?;
+            keys.insert(address.clone(), account.keys.into_inner());
+        }
+
+        let mut problems = Vec::new();
+        let mut problem = |address: &[u8], kind| {
+            problems.push(SignerProblem {
+                address: address.into(),
+                kind,
+            })
+        };
+
+        let mut stream = RlpStream::new();
+        rlp_encode_transaction_payload(
+            &mut stream,
+            &self.script,
+            &self.arguments,
+            &self.reference_block_id,
+            self.gas_limit,
+            proposer,
+            self.proposal_key.key_id.into(),
+            self.proposal_key.sequence_number,
+            payer,
+            &self.authorizers,
+        );
+        let mut payload = PADDED_TRANSACTION_DOMAIN_TAG.to_vec();
+        payload.extend_from_slice(&stream.out());
+
+        let mut stream = RlpStream::new();
+        rlp_encode_transaction_envelope(
+            &mut stream,
+            &self.script,
+            &self.arguments,
+            &self.reference_block_id,
+            self.gas_limit,
+            proposer,
+            self.proposal_key.key_id.into(),
+            self.proposal_key.sequence_number,
+            payer,
+            &self.authorizers,
+            self.payload_signatures
+                .iter()
+                .filter_map(|sig| {
+                    let signer_index = *signer_map.get(&sig.address)?;
+                    Some((signer_index, sig.key_id, &sig.signature))
+                })
+                .collect::<Vec<_>>(),
+        );
+        let mut envelope = PADDED_TRANSACTION_DOMAIN_TAG.to_vec();
+        envelope.extend_from_slice(&stream.out());
+
+        // The keys that made valid signatures, by account.
+        let mut signed: HashMap<&[u8], HashSet<u32>> = HashMap::new();
+        let mut seen = HashSet::new();
+
+        let payload_signatures = self.payload_signatures.iter().map(|sig| {
+            (
+                &*sig.address,
+                sig.key_id,
+                &sig.signature[..],
+                &payload,
+                false,
+            )
+        });
+        let envelope_signatures = self.envelope_signatures.iter().map(|sig| {
+            (
+                sig.address.as_ref(),
+                sig.key_id,
+                sig.signature.as_ref(),
+                &envelope,
+                true,
+            )
+        });
+
+        for (address, key_id, signature, data, is_envelope) in
+            payload_signatures.chain(envelope_signatures)
+        {
+            let expected = if is_envelope {
+                address == payer
+            } else {
+                address != payer
+                    && (address == proposer
+                        || self.authorizers.iter().any(|auth| &**auth == address))
+            };
+
+            if !expected {
+                problem(address, ProblemKind::UnexpectedSignature { key_id });
+                continue;
+            }
+
+            if !seen.insert((address, key_id)) {
+                problem(address, ProblemKind::DuplicateKey(key_id));
+                continue;
+            }
+
+            let key = match find_key(&keys[address], key_id) {
+                Some(key) => key,
+                None => {
+                    problem(address, ProblemKind::KeyNotFound(key_id));
+                    continue;
+                }
+            };
+
+            match key.verify(data, signature) {
+                Ok(()) => {
+                    signed.entry(address).or_default().insert(key_id);
+                }
+                Err(error) => problem(address, ProblemKind::InvalidSignature { key_id, error }),
+            }
+        }
+
+        match find_key(&keys[proposer], self.proposal_key.key_id) {
+            None => problem(
+                proposer,
+                ProblemKind::ProposalKeyNotFound(self.proposal_key.key_id),
+            ),
+            Some(key) if key.revoked => problem(proposer, ProblemKind::ProposalKeyRevoked),
+            Some(key) => {
+                if u64::from(key.sequence_number) != self.proposal_key.sequence_number {
+                    problem(
+                        proposer,
+                        ProblemKind::SequenceNumberMismatch {
+                            on_chain: key.sequence_number.into(),
+                            proposed: self.proposal_key.sequence_number,
+                        },
+                    );
+                }
+
+                let signed_by_proposal_key = signed
+                    .get(proposer)
+                    .is_some_and(|key_ids| key_ids.contains(&self.proposal_key.key_id));
+                if !signed_by_proposal_key {
+                    problem(proposer, ProblemKind::MissingProposalKeySignature);
+                }
+            }
+        }
+
+        // The payer and the authorizers, each once.
+        let mut full_weight_signers = vec![payer];
+        for authorizer in self.authorizers.iter() {
+            if !full_weight_signers.contains(&&**authorizer) {
+                full_weight_signers.push(authorizer);
+            }
+        }
+
+        for address in full_weight_signers {
+            let weight: u32 = signed
+                .get(address)
+                .into_iter()
+                .flatten()
+                .filter_map(|&key_id| find_key(&keys[address], key_id))
+                .map(|key| key.weight)
+                .sum();
+
+            if weight < FULL_WEIGHT {
+                problem(address, ProblemKind::NotEnoughWeight(weight));
+            }
+        }
+
+        Ok(problems)
+    }
+}
+
+fn find_key(keys: &[AccountKey], key_id: u32) -> Option<&AccountKey> {
+    keys.iter().find(|key| key.index == key_id)
+}